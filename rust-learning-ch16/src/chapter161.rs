@@ -48,7 +48,7 @@
 // mensagens enquanto uma nova thread imprime outras mensagens:
 
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub fn spawn_thread() {
     thread::spawn(|| {
@@ -109,4 +109,1296 @@ pub fn handle_thread() {
 }
 
 // Usar move garante que os dados transferidos para a thread criada não sejam usados novamente na
-// thread principal, evitando problemas de posse.
\ No newline at end of file
+// thread principal, evitando problemas de posse.
+
+// Escalonador Cooperativo de Tarefas
+
+// Threads do sistema operacional são preemptivas: o sistema decide quando cada uma roda. Em um
+// escalonador cooperativo, é a própria tarefa que devolve o controle. Abaixo, o Scheduler guarda
+// closures em uma fila; cada chamada executa um passo da tarefa e retorna true se ainda há trabalho
+// ou false quando terminou. Uma única thread trabalhadora percorre a fila em round-robin,
+// recolocando no final as tarefas que ainda não acabaram.
+
+use std::collections::VecDeque;
+
+type Task = Box<dyn FnMut() -> bool + Send>;
+
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: VecDeque<Task>,
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler::default()
+    }
+
+    pub fn add_task<F>(&mut self, task: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        self.tasks.push_back(Box::new(task));
+    }
+
+    pub fn run(&mut self) -> usize {
+        let mut tasks = std::mem::take(&mut self.tasks);
+
+        let handle = thread::spawn(move || {
+            let mut steps = 0;
+            while let Some(mut task) = tasks.pop_front() {
+                steps += 1;
+                if task() {
+                    tasks.push_back(task);
+                }
+            }
+            steps
+        });
+
+        handle.join().unwrap()
+    }
+}
+
+// Cada chamada de uma tarefa conta como um passo, inclusive a última, que retorna false. Como as
+// tarefas precisam atravessar para a thread trabalhadora, exigimos que elas sejam Send.
+
+// Reiniciando Todo o Grupo de Threads em Caso de Falha
+
+// Às vezes o trabalho de um grupo de threads só faz sentido se todas terminarem com sucesso: se
+// uma falhar, o resultado parcial das outras é descartado e o grupo inteiro recomeça. Esse estilo
+// de supervisão é conhecido como "tudo ou nada". Abaixo, cada thread devolve um Result pelo join;
+// se alguma retornar Err (ou entrar em pânico), a tentativa é descartada e uma nova é iniciada.
+// A falha é simulada apenas na tentativa de número fail_on_attempt.
+
+pub fn all_or_restart(threads: usize, fail_on_attempt: usize) -> usize {
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+
+        let handles: Vec<_> = (0..threads)
+            .map(|i| {
+                thread::spawn(move || {
+                    if i == 0 && attempt == fail_on_attempt {
+                        Err(format!("thread {i} falhou na tentativa {attempt}"))
+                    } else {
+                        Ok(())
+                    }
+                })
+            })
+            .collect();
+
+        let results: Vec<bool> = handles
+            .into_iter()
+            .map(|handle| matches!(handle.join(), Ok(Ok(()))))
+            .collect();
+
+        if results.into_iter().all(|ok| ok) {
+            return attempt;
+        }
+    }
+}
+
+// Coletamos os resultados em um vetor antes de verificar se todos tiveram sucesso. Isso garante que
+// todas as threads sejam aguardadas com join, mesmo depois de encontrar uma falha; chamar all
+// diretamente sobre os joins pararia na primeira falha e deixaria threads para trás.
+
+// Simulando Downloads com Paralelismo Limitado
+
+// Criar uma thread para cada tarefa funciona quando são poucas, mas com centenas de downloads
+// estaríamos abrindo centenas de conexões ao mesmo tempo. Uma forma simples de limitar o
+// paralelismo é processar as tarefas em lotes: criamos no máximo concurrency threads, esperamos
+// todas com join e só então começamos o próximo lote. Cada "download" dorme um tempo derivado do
+// tamanho da URL e devolve (url, bytes).
+
+pub fn simulate_downloads(urls: Vec<String>, concurrency: usize) -> Vec<(String, u64)> {
+    let mut results = Vec::new();
+
+    for batch in urls.chunks(concurrency.max(1)) {
+        let handles: Vec<_> = batch
+            .iter()
+            .cloned()
+            .map(|url| {
+                thread::spawn(move || {
+                    let len = url.len() as u64;
+                    thread::sleep(Duration::from_millis(len));
+                    (url, len * 1024)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            results.push(handle.join().unwrap());
+        }
+    }
+
+    results
+}
+
+// A desvantagem dos lotes é que o lote inteiro espera pelo download mais lento dele. Uma fila de
+// trabalho compartilhada entre concurrency workers aproveita melhor o tempo, mas exige Arc e Mutex,
+// que aparecem na seção sobre estado compartilhado.
+
+// Fan-Out Genérico: Aplicando uma Closure em Várias Threads
+
+// Juntando thread::spawn e join, conseguimos escrever um "map paralelo" genérico: cada item vai
+// para sua própria thread, a closure é aplicada lá, e os JoinHandle são aguardados na mesma ordem
+// em que foram criados, então o resultado preserva a ordem da entrada. A closure é compartilhada
+// entre as threads por um Arc, por isso ela precisa ser Send + Sync.
+
+use std::sync::Arc;
+
+pub fn fan_out<T, R, F>(items: Vec<T>, f: F) -> Vec<R>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+{
+    let f = Arc::new(f);
+
+    let handles: Vec<_> = items
+        .into_iter()
+        .map(|item| {
+            let f = Arc::clone(&f);
+            thread::spawn(move || f(item))
+        })
+        .collect();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+// Map Paralelo com Cancelamento no Primeiro Erro
+
+// Quando a closure pode falhar, muitas vezes não faz sentido continuar processando os outros itens
+// depois do primeiro erro. Em try_fan_out, um número fixo de workers retira itens de uma fila
+// compartilhada; antes de pegar cada item, o worker consulta um AtomicBool de cancelamento. O
+// primeiro worker que encontra um Err liga essa flag e guarda o erro, e os demais param assim que
+// terminam o item atual.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+pub fn try_fan_out<T, R, F>(items: Vec<T>, f: F) -> Result<Vec<R>, String>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> Result<R, String> + Send + Sync + 'static,
+{
+    let total = items.len();
+    let queue: Arc<Mutex<VecDeque<(usize, T)>>> =
+        Arc::new(Mutex::new(items.into_iter().enumerate().collect()));
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let first_error = Arc::new(Mutex::new(None));
+    let f = Arc::new(f);
+    let workers = thread::available_parallelism()
+        .map_or(4, |n| n.get())
+        .min(total.max(1));
+
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let cancelled = Arc::clone(&cancelled);
+            let first_error = Arc::clone(&first_error);
+            let f = Arc::clone(&f);
+            thread::spawn(move || {
+                let mut done = vec![];
+                while !cancelled.load(Ordering::SeqCst) {
+                    let Some((index, item)) = queue.lock().unwrap().pop_front() else {
+                        break;
+                    };
+                    match f(item) {
+                        Ok(result) => done.push((index, result)),
+                        Err(e) => {
+                            if !cancelled.swap(true, Ordering::SeqCst) {
+                                *first_error.lock().unwrap() = Some(e);
+                            }
+                            break;
+                        }
+                    }
+                }
+                done
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(total);
+    for handle in handles {
+        results.extend(handle.join().unwrap());
+    }
+
+    if let Some(e) = first_error.lock().unwrap().take() {
+        return Err(e);
+    }
+
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+// O swap devolve o valor anterior da flag, então apenas o worker que a mudou de false para true
+// grava o erro: mesmo que dois itens falhem quase ao mesmo tempo, só o primeiro é relatado.
+
+// Dividir para Conquistar com Threads com Escopo
+
+// thread::scope cria um escopo em que as threads podem pegar emprestado dados da pilha, porque o
+// escopo só termina depois que todas elas forem aguardadas. Ele também serve para paralelismo
+// recursivo: abaixo, cada chamada de parallel_fib acima do limite (threshold) calcula um dos
+// subproblemas em uma nova thread com escopo e o outro na thread atual. Abaixo do limite, o cálculo
+// é sequencial, o que evita criar uma quantidade exponencial de threads para problemas pequenos.
+
+fn fib(n: u32) -> u64 {
+    if n < 2 {
+        n as u64
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+pub fn parallel_fib(n: u32, threshold: u32) -> u64 {
+    if n <= threshold || n < 2 {
+        return fib(n);
+    }
+
+    thread::scope(|s| {
+        let left = s.spawn(|| parallel_fib(n - 1, threshold));
+        let right = parallel_fib(n - 2, threshold);
+        left.join().unwrap() + right
+    })
+}
+
+// Processando Linhas em Pedaços Paralelos
+
+// Para processar um arquivo grande em paralelo, podemos dividir as linhas em pedaços contíguos,
+// um por worker. Cada worker devolve o resultado do seu pedaço pelo join, e como os JoinHandle são
+// aguardados na mesma ordem em que os pedaços foram criados, basta concatenar os resultados para
+// manter a ordem original. Se houver mais workers que linhas, cada pedaço tem uma linha e os
+// workers extras simplesmente não são criados.
+
+pub fn process_lines(lines: Vec<String>, workers: usize) -> Vec<usize> {
+    let chunk_size = lines.len().div_ceil(workers.max(1)).max(1);
+
+    let handles: Vec<_> = lines
+        .chunks(chunk_size)
+        .map(|chunk| {
+            let chunk = chunk.to_vec();
+            thread::spawn(move || chunk.iter().map(|line| line.len()).collect::<Vec<_>>())
+        })
+        .collect();
+
+    handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+// Acumulando em Estado Local da Thread
+
+// A macro thread_local! declara uma variável da qual cada thread tem sua própria cópia. Como
+// nenhuma outra thread enxerga essa cópia, ela pode ser atualizada sem bloqueios nem atômicos. Um
+// padrão de alto desempenho é acumular localmente durante o trabalho e só no final enviar o total
+// de cada thread pelo canal para ser somado.
+
+use std::cell::Cell;
+use std::sync::mpsc;
+
+thread_local! {
+    static LOCAL_TOTAL: Cell<i64> = const { Cell::new(0) };
+}
+
+pub fn local_accumulate(threads: usize, per_thread: usize) -> i64 {
+    let (tx, rx) = mpsc::channel();
+
+    for _ in 0..threads {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for _ in 0..per_thread {
+                LOCAL_TOTAL.with(|total| total.set(total.get() + 1));
+            }
+            tx.send(LOCAL_TOTAL.with(Cell::get)).unwrap();
+        });
+    }
+    drop(tx);
+
+    rx.iter().sum()
+}
+
+// Cada thread começa com LOCAL_TOTAL igual a 0, independentemente do que as outras fizeram, e o
+// valor é descartado quando a thread termina.
+
+// Intercalação Reproduzível com Atraso Pseudoaleatório
+
+// Bugs de concorrência dependem da ordem em que as threads rodam, e essa ordem muda a cada
+// execução. Para estudar intercalações, é útil poder reproduzi-las. A função abaixo repete o
+// padrão de spawn_thread (uma thread criada com 's' e a principal com 'm'), mas cada passo dorme um
+// tempo sorteado por um gerador congruencial linear (LCG) a partir de seed. Cada evento é
+// registrado com o instante "lógico" em que aconteceu (a soma dos atrasos sorteados até ali), e a
+// ordem final é obtida ordenando por esse instante. Assim, a mesma seed sempre produz a mesma
+// intercalação, independentemente do agendamento real do sistema operacional.
+
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+}
+
+fn jittered_events(tag: char, steps: usize, seed: u64) -> Vec<(u64, char)> {
+    let mut rng = Lcg(seed);
+    let mut clock = 0;
+    let mut events = Vec::with_capacity(steps);
+    for _ in 0..steps {
+        let jitter = rng.next() % 5;
+        thread::sleep(Duration::from_millis(jitter));
+        clock += jitter;
+        events.push((clock, tag));
+    }
+    events
+}
+
+pub fn jittered_threads(seed: u64) -> Vec<char> {
+    let handle = thread::spawn(move || jittered_events('s', 9, seed));
+    let mut events = jittered_events('m', 4, seed ^ 0x5DEECE66D);
+    events.extend(handle.join().unwrap());
+
+    events.sort();
+    events.into_iter().map(|(_, tag)| tag).collect()
+}
+
+// Trabalho Dentro de um Orçamento de Quadro
+
+// Em jogos e aplicações gráficas, cada quadro (frame) tem um orçamento fixo de tempo, por exemplo
+// 16 ms para 60 quadros por segundo. O trabalho que não cabe no quadro atual é adiado para o
+// próximo. Abaixo, uma thread trabalhadora executa as tarefas em ordem (cada uma dorme seus
+// milissegundos) e, antes de começar cada tarefa, verifica se o tempo já gasto mais o custo da
+// tarefa ultrapassaria o orçamento. A partir da primeira que não cabe, todas são adiadas.
+
+pub fn frame_budget(tasks: Vec<u64>, budget_ms: u64) -> (Vec<u64>, Vec<u64>) {
+    let handle = thread::spawn(move || {
+        let budget = Duration::from_millis(budget_ms);
+        let start = Instant::now();
+        let mut completed = Vec::new();
+        let mut deferred = Vec::new();
+
+        for task in tasks {
+            let cost = Duration::from_millis(task);
+            if !deferred.is_empty() || start.elapsed() + cost > budget {
+                deferred.push(task);
+                continue;
+            }
+            thread::sleep(cost);
+            completed.push(task);
+        }
+
+        (completed, deferred)
+    });
+
+    handle.join().unwrap()
+}
+
+// As tarefas adiadas mantêm a ordem original, para que o próximo quadro continue exatamente de
+// onde este parou.
+
+// Quicksort Paralelo com Partição no Próprio Vetor
+
+// O quicksort escolhe um pivô, reorganiza o vetor para que os menores fiquem à esquerda e os
+// maiores à direita e depois ordena as duas metades. Como as metades não se sobrepõem,
+// split_at_mut nos dá duas fatias mutáveis independentes, e o compilador aceita ordenar cada uma em
+// uma thread com escopo diferente. O parâmetro depth limita quantos níveis da recursão criam
+// threads; abaixo disso, a ordenação continua sequencial na thread atual.
+
+fn partition(data: &mut [i32]) -> usize {
+    let last = data.len() - 1;
+    data.swap(data.len() / 2, last);
+    let pivot = data[last];
+
+    let mut store = 0;
+    for i in 0..last {
+        if data[i] < pivot {
+            data.swap(i, store);
+            store += 1;
+        }
+    }
+    data.swap(store, last);
+    store
+}
+
+pub fn parallel_quicksort(data: &mut [i32], depth: usize) {
+    if data.len() <= 1 {
+        return;
+    }
+
+    let pivot = partition(data);
+    let (left, right) = data.split_at_mut(pivot);
+    let right = &mut right[1..];
+
+    if depth == 0 {
+        parallel_quicksort(left, 0);
+        parallel_quicksort(right, 0);
+    } else {
+        thread::scope(|s| {
+            s.spawn(|| parallel_quicksort(left, depth - 1));
+            parallel_quicksort(right, depth - 1);
+        });
+    }
+}
+
+// A função recebe &mut [i32] em vez de &mut Vec<i32>: um &mut Vec<i32> é convertido
+// automaticamente em fatia, e a recursão precisa mesmo trabalhar sobre pedaços do vetor. Com depth
+// igual a d, no máximo 2^d - 1 threads extras são criadas, o que evita o custo de abrir uma thread
+// para cada pedacinho do vetor.
+
+// Soma de Prefixos Paralela (Scan)
+
+// A soma de prefixos transforma [a, b, c] em [a, a + b, a + b + c]. Parece inerentemente
+// sequencial, já que cada posição depende da anterior, mas pode ser feita em duas fases. Na
+// primeira, cada thread calcula as somas de prefixos locais do seu pedaço. Depois, somando os
+// totais dos pedaços anteriores, descobrimos quanto precisa ser acrescentado a cada pedaço. Na
+// segunda fase, cada thread soma esse deslocamento a todos os elementos do seu pedaço.
+
+pub fn parallel_prefix_sum(mut data: Vec<i64>, workers: usize) -> Vec<i64> {
+    let chunk_size = data.len().div_ceil(workers.max(1)).max(1);
+
+    thread::scope(|s| {
+        for chunk in data.chunks_mut(chunk_size) {
+            s.spawn(move || {
+                for i in 1..chunk.len() {
+                    chunk[i] += chunk[i - 1];
+                }
+            });
+        }
+    });
+
+    let mut offsets = Vec::new();
+    let mut running = 0;
+    for chunk in data.chunks(chunk_size) {
+        offsets.push(running);
+        running += chunk.last().copied().unwrap_or(0);
+    }
+
+    thread::scope(|s| {
+        for (chunk, offset) in data.chunks_mut(chunk_size).zip(offsets) {
+            s.spawn(move || {
+                for value in chunk {
+                    *value += offset;
+                }
+            });
+        }
+    });
+
+    data
+}
+
+// Entre as duas fases há um pequeno trecho sequencial, mas ele percorre apenas um valor por
+// pedaço, não o vetor inteiro.
+
+// Watchdog que Interrompe um Cálculo Descontrolado
+
+// Rust não permite matar uma thread de fora. A alternativa é a preempção cooperativa: a thread
+// trabalhadora consulta periodicamente uma flag compartilhada e para por conta própria quando ela
+// é ligada. Abaixo, um watchdog espera até max_ms pelo aviso de que o trabalho terminou; se o
+// aviso não chegar a tempo, ele liga a flag. O worker então para e devolve quantas iterações
+// conseguiu fazer, e a função retorna esse total parcial em Ok. Além do prazo, existe um teto
+// fixo de iterações: um trabalho que pede mais do que RUNAWAY_HARD_CAP para ao atingi-lo, e aí a
+// função retorna Err.
+
+const RUNAWAY_HARD_CAP: usize = 200;
+
+pub fn runaway_guard(work_iters: usize, max_ms: u64) -> Result<usize, String> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let watchdog = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            if done_rx.recv_timeout(Duration::from_millis(max_ms)).is_err() {
+                stop.store(true, Ordering::SeqCst);
+            }
+        })
+    };
+
+    let worker = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut completed = 0;
+            let limit = work_iters.min(RUNAWAY_HARD_CAP);
+            while completed < limit && !stop.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(1));
+                completed += 1;
+            }
+            let _ = done_tx.send(());
+            completed
+        })
+    };
+
+    let completed = worker.join().unwrap();
+    watchdog.join().unwrap();
+
+    if completed >= RUNAWAY_HARD_CAP && work_iters > RUNAWAY_HARD_CAP {
+        Err(format!(
+            "teto de {RUNAWAY_HARD_CAP} iterações excedido: foram pedidas {work_iters}"
+        ))
+    } else {
+        Ok(completed)
+    }
+}
+
+// Quanto mais frequente a verificação da flag, mais rápido o worker responde ao watchdog; aqui ela
+// é consultada a cada iteração.
+
+// Escopos Aninhados: Threads Dentro de Threads
+
+// thread::scope pode ser usado dentro de uma thread que já pertence a outro escopo. Cada nível
+// pode pegar emprestados dados da pilha de quem o criou, porque todos os escopos internos terminam
+// antes do externo. Abaixo, duas threads externas recebem cada uma metade do array data; cada uma
+// abre o seu próprio escopo com uma thread interna por elemento, que devolve o quadrado do
+// elemento. A thread externa soma esses quadrados e acrescenta uma contribuição própria (o tamanho
+// da sua metade), e a thread principal soma o resultado das externas.
+
+pub fn nested_scopes() -> i32 {
+    let data = [1, 2, 3, 4, 5, 6];
+
+    thread::scope(|outer| {
+        let handles: Vec<_> = data
+            .chunks(3)
+            .map(|half| {
+                outer.spawn(move || {
+                    let inner_total: i32 = thread::scope(|inner| {
+                        let handles: Vec<_> =
+                            half.iter().map(|x| inner.spawn(move || x * x)).collect();
+                        handles.into_iter().map(|h| h.join().unwrap()).sum()
+                    });
+                    inner_total + half.len() as i32
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+// O resultado é a soma dos quadrados (1 + 4 + 9 + 16 + 25 + 36 = 91) mais 3 de cada thread
+// externa, ou seja, 97. Nenhum nível precisou de Arc nem de clonar data.
+
+// Entregando Resultados por Meio de uma Closure de Callback
+
+// Canais não são a única forma de devolver resultados de uma thread. Outra opção é passar uma
+// closure (callback) que a própria thread trabalhadora chama a cada valor produzido. Como a
+// closure é executada em outra thread, ela precisa ser Send, e qualquer estado que ela altere,
+// como um vetor de resultados, precisa estar protegido, por exemplo com Arc<Mutex<...>>.
+
+pub fn with_callback<F: Fn(i32) + Send + Sync + 'static>(count: usize, callback: F) {
+    let worker = thread::spawn(move || {
+        for value in 0..count as i32 {
+            callback(value);
+        }
+    });
+
+    worker.join().unwrap();
+}
+
+// Diferente de um canal, o callback roda na thread trabalhadora: se ele for lento, é o produtor
+// que fica mais lento, não quem consome.
+
+// join Retorna Err Quando a Thread Entra em Pânico
+
+// Nos exemplos anteriores chamamos handle.join().unwrap(), o que esconde um detalhe importante:
+// join devolve um Result. Se a thread criada entrar em pânico, o pânico não derruba a thread
+// principal; em vez disso, join retorna Err com o conteúdo do pânico, e quem chamou decide o que
+// fazer.
+
+pub fn join_detects_panic() -> bool {
+    let handle = thread::spawn(|| {
+        panic!("a thread criada entrou em pânico");
+    });
+
+    handle.join().is_err()
+}
+
+// A mensagem do pânico ainda aparece na saída de erro, mas a thread principal continua rodando
+// normalmente depois do join.
+
+// Coletando Resultados com Erros Tipados
+
+// Uma thread pode terminar de três formas: com sucesso, devolvendo um Err próprio, ou entrando em
+// pânico. join mistura isso em um Result<Result<u64, String>, ...>, o que fica confuso de tratar.
+// Um enum de erro próprio deixa os casos explícitos: WorkerError::Panicked quando join retorna Err
+// e WorkerError::ReturnedError quando a thread terminou normalmente, mas devolveu Err. Abaixo, os
+// workers alternam entre os três comportamentos conforme o índice.
+
+#[derive(Debug, PartialEq)]
+pub enum WorkerError {
+    Panicked,
+    ReturnedError(String),
+}
+
+pub fn collect_typed(threads: usize) -> Vec<Result<u64, WorkerError>> {
+    let handles: Vec<_> = (0..threads)
+        .map(|i| {
+            thread::spawn(move || -> Result<u64, String> {
+                match i % 3 {
+                    0 => Ok(i as u64 * 10),
+                    1 => Err(format!("worker {i} falhou")),
+                    _ => panic!("worker {i} entrou em pânico"),
+                }
+            })
+        })
+        .collect();
+
+    handles
+        .into_iter()
+        .map(|handle| match handle.join() {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) => Err(WorkerError::ReturnedError(e)),
+            Err(_) => Err(WorkerError::Panicked),
+        })
+        .collect()
+}
+
+// For Paralelo Sobre um Intervalo Numérico
+
+// Um laço for sobre um intervalo grande pode ser dividido entre workers: o intervalo [start, end) é
+// quebrado em workers pedaços contíguos de tamanhos parecidos, e cada worker aplica f a todos os
+// números do seu pedaço. Como f é compartilhada entre threads por um Arc, ela precisa ser Send +
+// Sync. Um intervalo vazio não cria nenhuma thread.
+
+pub fn par_for_each_range<F: Fn(u64) + Send + Sync + 'static>(
+    start: u64,
+    end: u64,
+    workers: usize,
+    f: F,
+) {
+    if start >= end {
+        return;
+    }
+
+    let f = Arc::new(f);
+    let len = end - start;
+    let chunk = len.div_ceil(workers.max(1) as u64);
+
+    let handles: Vec<_> = (start..end)
+        .step_by(chunk as usize)
+        .map(|chunk_start| {
+            let chunk_end = (chunk_start + chunk).min(end);
+            let f = Arc::clone(&f);
+            thread::spawn(move || {
+                for i in chunk_start..chunk_end {
+                    f(i);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+// Estacionando e Acordando uma Thread com park e unpark
+
+// thread::park bloqueia a thread atual até que alguém chame unpark no seu handle. São as
+// primitivas de mais baixo nível para suspender uma thread, sobre as quais canais e Mutexes podem
+// ser construídos. park pode retornar sem motivo (um despertar espúrio), então a thread sempre
+// confere uma condição em laço. Abaixo, o worker estaciona até a thread principal liberá-lo, e
+// então marca em uma flag atômica que acordou.
+
+pub fn park_unpark_demo() -> bool {
+    let released = Arc::new(AtomicBool::new(false));
+    let woke = Arc::new(AtomicBool::new(false));
+
+    let handle = {
+        let released = Arc::clone(&released);
+        let woke = Arc::clone(&woke);
+        thread::spawn(move || {
+            while !released.load(Ordering::Acquire) {
+                thread::park();
+            }
+            woke.store(true, Ordering::Release);
+        })
+    };
+
+    thread::sleep(Duration::from_millis(20));
+    released.store(true, Ordering::Release);
+    handle.thread().unpark();
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while !woke.load(Ordering::Acquire) && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(1));
+    }
+    let result = woke.load(Ordering::Acquire);
+    handle.join().unwrap();
+    result
+}
+
+// Se unpark for chamado antes de park, o "token" fica guardado e o próximo park retorna na hora;
+// por isso não há corrida entre liberar a flag e o worker chegar ao park.
+
+// Recuperando o Trabalho Parcial de um Escopo com Pânico
+
+// Ao final de thread::scope, todas as threads são aguardadas; se alguma delas entrou em pânico, o
+// próprio scope propaga o pânico para quem o chamou. Com std::panic::catch_unwind podemos capturar
+// esse pânico e seguir em frente. Como as threads que terminaram bem gravaram seus resultados em um
+// Arc<Mutex<Vec<usize>>> que vive fora do escopo, esse trabalho parcial continua disponível.
+
+use std::panic::{self, AssertUnwindSafe};
+
+pub fn scope_partial_recovery(threads: usize, panic_index: usize) -> Vec<usize> {
+    let results = Arc::new(Mutex::new(Vec::new()));
+
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        thread::scope(|s| {
+            for i in 0..threads {
+                let results = Arc::clone(&results);
+                s.spawn(move || {
+                    if i == panic_index {
+                        panic!("thread {i} entrou em pânico");
+                    }
+                    results.lock().unwrap().push(i);
+                });
+            }
+        });
+    }));
+
+    let mut partial = results.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    partial.sort();
+    partial
+}
+
+// AssertUnwindSafe diz ao compilador que aceitamos observar o estado depois de um pânico. Aqui isso
+// é seguro: a thread que entra em pânico nunca segura o bloqueio, então o Mutex não fica envenenado.
+// O resultado de catch_unwind é descartado de propósito: quem chama percebe a falha porque o vetor
+// devolvido tem menos resultados do que threads.
+
+// Produto Escalar Paralelo
+
+// O produto escalar de dois vetores é a soma dos produtos par a par, então ele se divide
+// naturalmente: cortamos os dois slices nas mesmas posições, cada thread com escopo calcula o
+// produto escalar do seu par de pedaços e, no fim, somamos os parciais. Como thread::scope permite
+// emprestar os slices, nada é copiado.
+
+pub fn parallel_dot(a: &[f64], b: &[f64], workers: usize) -> f64 {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "os vetores precisam ter o mesmo tamanho para o produto escalar"
+    );
+    if a.is_empty() {
+        return 0.0;
+    }
+    let chunk_size = a.len().div_ceil(workers.max(1));
+
+    thread::scope(|s| {
+        let handles: Vec<_> = a
+            .chunks(chunk_size)
+            .zip(b.chunks(chunk_size))
+            .map(|(x, y)| s.spawn(move || x.iter().zip(y).map(|(x, y)| x * y).sum::<f64>()))
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+// A soma de ponto flutuante não é associativa: somar em outra ordem pode mudar os últimos dígitos.
+// Por isso o resultado paralelo pode diferir muito levemente do sequencial.
+
+// Cedendo a Vez com thread::yield_now
+
+// thread::yield_now avisa o escalonador do sistema operacional de que a thread atual pode ceder o
+// restante da sua fatia de tempo. É só uma sugestão: o sistema pode voltar a rodar a mesma thread
+// na hora. Mesmo assim, chamar yield_now entre unidades de trabalho costuma aumentar a
+// intercalação entre threads. Cada thread registra seu id em um log compartilhado a cada unidade
+// concluída; a posição no log funciona como número de sequência global.
+
+const YIELD_UNITS: usize = 5;
+
+pub fn yield_demo(threads: usize) -> Vec<usize> {
+    let log = Arc::new(Mutex::new(Vec::with_capacity(threads * YIELD_UNITS)));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|id| {
+            let log = Arc::clone(&log);
+            thread::spawn(move || {
+                for _ in 0..YIELD_UNITS {
+                    log.lock().unwrap().push(id);
+                    thread::yield_now();
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let order = log.lock().unwrap().clone();
+    order
+}
+
+// Sem yield_now, uma thread curta como essas costuma terminar todas as unidades de uma vez antes
+// que a próxima sequer comece; com ele, o log tende a alternar mais entre os ids.
+
+// Busca Paralela que Para Cedo
+
+// Para encontrar a primeira ocorrência de um valor em um vetor grande, dividimos o vetor entre
+// workers e cada um percorre o seu pedaço. Quando alguém encontra o alvo, envia o índice pelo canal
+// e avisa os outros por um valor compartilhado. Um simples AtomicBool "achei" não basta: um worker
+// de um pedaço anterior ainda pode encontrar um índice menor. Por isso o sinal é um AtomicUsize com
+// o menor índice encontrado até agora (fetch_min), e cada worker só desiste quando a sua posição já
+// passou desse índice.
+
+use std::sync::atomic::AtomicUsize;
+
+pub fn parallel_find(data: Vec<i32>, target: i32, workers: usize) -> Option<usize> {
+    if data.is_empty() {
+        return None;
+    }
+    let chunk_size = data.len().div_ceil(workers.max(1));
+    let best = AtomicUsize::new(usize::MAX);
+    let (tx, rx) = mpsc::channel::<usize>();
+
+    thread::scope(|s| {
+        for (chunk_index, chunk) in data.chunks(chunk_size).enumerate() {
+            let tx = tx.clone();
+            let best = &best;
+            s.spawn(move || {
+                let offset = chunk_index * chunk_size;
+                for (i, value) in chunk.iter().enumerate() {
+                    let index = offset + i;
+                    if index > best.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    if *value == target {
+                        best.fetch_min(index, Ordering::Relaxed);
+                        tx.send(index).unwrap();
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    drop(tx);
+
+    rx.iter().min()
+}
+
+// Os workers dos pedaços finais costumam parar logo depois que um pedaço anterior encontra o alvo,
+// enquanto os anteriores continuam até o fim, já que ainda podem achar um índice menor.
+
+// O Custo de Criar Threads Versus Reutilizá-las em um Pool
+
+// Criar uma thread envolve pedir ao sistema operacional uma pilha nova e registrá-la no
+// escalonador. Para tarefas muito curtas, esse custo pode ser maior que o próprio trabalho. Um
+// pool de threads cria alguns workers uma única vez, e as tarefas são enviadas a eles por um canal
+// compartilhado, no mesmo estilo do receptor em Arc<Mutex<...>> usado com vários consumidores.
+// Abaixo, medimos o tempo para executar tasks tarefas triviais criando uma thread por tarefa e
+// submetendo as mesmas tarefas a um ThreadPool.
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+pub struct ThreadPool {
+    sender: Option<mpsc::Sender<Job>>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    pub fn new(size: usize) -> ThreadPool {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+
+        ThreadPool {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    pub fn execute<F: FnOnce() + Send + 'static>(&self, f: F) {
+        self.sender.as_ref().unwrap().send(Box::new(f)).unwrap();
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            worker.join().unwrap();
+        }
+    }
+}
+
+const POOL_WORKERS: usize = 4;
+
+pub fn spawn_vs_pool(tasks: usize) -> (Duration, Duration) {
+    let spawned = Arc::new(AtomicUsize::new(0));
+    let start = Instant::now();
+    let handles: Vec<_> = (0..tasks)
+        .map(|_| {
+            let spawned = Arc::clone(&spawned);
+            thread::spawn(move || {
+                spawned.fetch_add(1, Ordering::Relaxed);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let spawn_elapsed = start.elapsed();
+
+    let pooled = Arc::new(AtomicUsize::new(0));
+    let start = Instant::now();
+    {
+        let pool = ThreadPool::new(POOL_WORKERS);
+        for _ in 0..tasks {
+            let pooled = Arc::clone(&pooled);
+            pool.execute(move || {
+                pooled.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+    }
+    let pool_elapsed = start.elapsed();
+
+    assert_eq!(spawned.load(Ordering::Relaxed), tasks);
+    assert_eq!(pooled.load(Ordering::Relaxed), tasks);
+    (spawn_elapsed, pool_elapsed)
+}
+
+// O tempo do pool inclui criar e encerrar os próprios workers: o bloco termina com o drop do
+// ThreadPool, que fecha o canal e espera cada worker terminar as tarefas pendentes. Mesmo assim,
+// com milhares de tarefas o pool costuma ser bem mais rápido. Cada tarefa incrementa um contador,
+// e os assert_eq! no fim garantem que as duas estratégias executaram todas as tarefas antes de os
+// tempos serem devolvidos.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheduler_runs_tasks_round_robin() {
+        let mut scheduler = Scheduler::new();
+
+        let mut a = 0;
+        scheduler.add_task(move || {
+            a += 1;
+            a < 3
+        });
+        let mut b = 0;
+        scheduler.add_task(move || {
+            b += 1;
+            b < 5
+        });
+
+        assert_eq!(scheduler.run(), 8);
+        assert_eq!(scheduler.run(), 0);
+    }
+
+    #[test]
+    fn all_or_restart_retries_whole_group() {
+        assert_eq!(all_or_restart(4, 1), 2);
+        assert_eq!(all_or_restart(4, 5), 1);
+    }
+
+    #[test]
+    fn simulate_downloads_returns_every_url_once() {
+        let urls: Vec<String> = (0..7).map(|i| format!("https://example.com/{i}")).collect();
+        let results = simulate_downloads(urls.clone(), 3);
+
+        assert_eq!(results.len(), urls.len());
+        for url in &urls {
+            assert_eq!(results.iter().filter(|(u, _)| u == url).count(), 1);
+        }
+    }
+
+    #[test]
+    fn fan_out_maps_integers_to_strings() {
+        assert_eq!(
+            fan_out(vec![1, 2, 3], |x: i32| x.to_string()),
+            vec!["1", "2", "3"]
+        );
+    }
+
+    #[test]
+    fn fan_out_maps_strings_to_lengths() {
+        let words = vec![String::from("a"), String::from("abc"), String::from("ab")];
+        assert_eq!(fan_out(words, |s: String| s.len()), vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn try_fan_out_collects_results_in_order() {
+        let results = try_fan_out(vec![1, 2, 3, 4], |x: i32| Ok::<i32, String>(x * 10));
+        assert_eq!(results, Ok(vec![10, 20, 30, 40]));
+    }
+
+    #[test]
+    fn try_fan_out_stops_early_on_first_error() {
+        use std::sync::atomic::AtomicUsize;
+
+        let processed = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&processed);
+
+        let result = try_fan_out((0..50).collect(), move |x: i32| {
+            if x == 0 {
+                return Err(format!("item {x} falhou"));
+            }
+            thread::sleep(Duration::from_millis(10));
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(x)
+        });
+
+        assert_eq!(result, Err(String::from("item 0 falhou")));
+        assert!(processed.load(Ordering::SeqCst) < 49);
+    }
+
+    #[test]
+    fn parallel_fib_matches_sequential() {
+        assert_eq!(parallel_fib(10, 5), 55);
+        for n in 0..20 {
+            assert_eq!(parallel_fib(n, 8), fib(n));
+        }
+    }
+
+    #[test]
+    fn process_lines_preserves_order() {
+        let lines: Vec<String> = ["um", "dois", "três", "", "quatro"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let expected: Vec<usize> = lines.iter().map(|line| line.len()).collect();
+
+        assert_eq!(process_lines(lines.clone(), 2), expected);
+        assert_eq!(process_lines(lines, 10), expected);
+        assert!(process_lines(vec![], 3).is_empty());
+    }
+
+    #[test]
+    fn local_accumulate_merges_thread_totals() {
+        assert_eq!(local_accumulate(8, 1_000), 8_000);
+    }
+
+    #[test]
+    fn jittered_threads_is_deterministic_per_seed() {
+        let first = jittered_threads(42);
+        assert_eq!(first, jittered_threads(42));
+        assert_eq!(first.iter().filter(|&&tag| tag == 's').count(), 9);
+        assert_eq!(first.iter().filter(|&&tag| tag == 'm').count(), 4);
+
+        assert!((0..10).any(|seed| jittered_threads(seed) != first));
+    }
+
+    #[test]
+    fn frame_budget_defers_tasks_past_the_budget() {
+        let (completed, deferred) = frame_budget(vec![10, 10, 10, 100, 10], 50);
+        assert_eq!(completed, vec![10, 10, 10]);
+        assert_eq!(deferred, vec![100, 10]);
+    }
+
+    #[test]
+    fn parallel_quicksort_matches_std_sort() {
+        let mut rng = Lcg(7);
+        let mut data: Vec<i32> = (0..1_000)
+            .map(|_| (rng.next() % 500) as i32 - 250)
+            .collect();
+        let mut expected = data.clone();
+        expected.sort();
+
+        parallel_quicksort(&mut data, 3);
+        assert_eq!(data, expected);
+
+        let mut empty: Vec<i32> = vec![];
+        parallel_quicksort(&mut empty, 3);
+        assert!(empty.is_empty());
+
+        let mut single = vec![42];
+        parallel_quicksort(&mut single, 3);
+        assert_eq!(single, vec![42]);
+    }
+
+    #[test]
+    fn parallel_prefix_sum_matches_sequential_scan() {
+        let data: Vec<i64> = (1..=103)
+            .map(|x| x * if x % 3 == 0 { -1 } else { 1 })
+            .collect();
+        let expected: Vec<i64> = data
+            .iter()
+            .scan(0, |acc, &x| {
+                *acc += x;
+                Some(*acc)
+            })
+            .collect();
+
+        assert_eq!(parallel_prefix_sum(data.clone(), 4), expected);
+        assert_eq!(parallel_prefix_sum(data, 200), expected);
+        assert!(parallel_prefix_sum(vec![], 4).is_empty());
+    }
+
+    #[test]
+    fn runaway_guard_completes_within_budget() {
+        assert_eq!(runaway_guard(5, 5_000), Ok(5));
+    }
+
+    #[test]
+    fn runaway_guard_returns_partial_count_when_watchdog_fires() {
+        let completed = runaway_guard(190, 20).unwrap();
+        assert!(completed < 190, "{completed} iterações");
+    }
+
+    #[test]
+    fn runaway_guard_rejects_work_beyond_hard_cap() {
+        let result = runaway_guard(RUNAWAY_HARD_CAP * 10, 60_000);
+        assert!(result.unwrap_err().starts_with("teto de"));
+    }
+
+    #[test]
+    fn nested_scopes_sum_every_level() {
+        assert_eq!(nested_scopes(), 97);
+    }
+
+    #[test]
+    fn with_callback_invokes_callback_for_each_value() {
+        let collected = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&collected);
+
+        with_callback(4, move |value| sink.lock().unwrap().push(value));
+
+        assert_eq!(*collected.lock().unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn join_returns_err_for_panicking_thread() {
+        assert!(join_detects_panic());
+    }
+
+    #[test]
+    fn collect_typed_classifies_each_outcome() {
+        assert_eq!(
+            collect_typed(4),
+            vec![
+                Ok(0),
+                Err(WorkerError::ReturnedError(String::from("worker 1 falhou"))),
+                Err(WorkerError::Panicked),
+                Ok(30),
+            ]
+        );
+    }
+
+    #[test]
+    fn par_for_each_range_touches_every_value() {
+        use std::collections::HashSet;
+
+        let touched = Arc::new(Mutex::new(HashSet::new()));
+        let sink = Arc::clone(&touched);
+        par_for_each_range(10, 110, 7, move |i| {
+            sink.lock().unwrap().insert(i);
+        });
+        assert_eq!(
+            *touched.lock().unwrap(),
+            (10..110).collect::<HashSet<u64>>()
+        );
+
+        let untouched = Arc::new(Mutex::new(HashSet::new()));
+        let sink = Arc::clone(&untouched);
+        par_for_each_range(5, 5, 4, move |i| {
+            sink.lock().unwrap().insert(i);
+        });
+        assert!(untouched.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn park_unpark_demo_wakes_worker() {
+        assert!(park_unpark_demo());
+    }
+
+    #[test]
+    fn scope_partial_recovery_keeps_finished_results() {
+        assert_eq!(scope_partial_recovery(5, 2), vec![0, 1, 3, 4]);
+        assert_eq!(scope_partial_recovery(3, 10), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn parallel_dot_matches_sequential() {
+        let a: Vec<f64> = (0..10_000).map(|i| (i % 97) as f64 * 0.5).collect();
+        let b: Vec<f64> = (0..10_000).map(|i| ((i * 7) % 31) as f64 - 15.0).collect();
+        let expected: f64 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+
+        assert!((parallel_dot(&a, &b, 4) - expected).abs() < 1e-6);
+        assert_eq!(parallel_dot(&[], &[], 4), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "mesmo tamanho")]
+    fn parallel_dot_rejects_length_mismatch() {
+        parallel_dot(&[1.0, 2.0], &[1.0], 2);
+    }
+
+    #[test]
+    fn yield_demo_records_every_thread_event() {
+        let order = yield_demo(4);
+        assert_eq!(order.len(), 4 * YIELD_UNITS);
+        for id in 0..4 {
+            assert_eq!(order.iter().filter(|&&x| x == id).count(), YIELD_UNITS);
+        }
+    }
+
+    #[test]
+    fn parallel_find_returns_smallest_matching_index() {
+        let mut data: Vec<i32> = (0..1_000).collect();
+        data[700] = -1;
+        data[950] = -1;
+        assert_eq!(parallel_find(data.clone(), -1, 4), Some(700));
+        assert_eq!(parallel_find(data, 700, 4), None);
+    }
+
+    #[test]
+    fn parallel_find_prefers_earlier_chunk_even_if_it_finishes_later() {
+        // Com 4 workers, cada pedaço tem 250 elementos: o pedaço 1 acha o alvo no primeiro
+        // elemento, enquanto o pedaço 0 só chega ao seu no índice 240.
+        let mut data: Vec<i32> = (0..1_000).collect();
+        data[240] = -1;
+        data[250] = -1;
+        data[600] = -1;
+        assert_eq!(parallel_find(data, -1, 4), Some(240));
+    }
+
+    #[test]
+    fn parallel_find_returns_none_when_absent() {
+        assert_eq!(parallel_find((0..100).collect(), -1, 3), None);
+        assert_eq!(parallel_find(vec![], 1, 3), None);
+    }
+
+    #[test]
+    fn thread_pool_runs_every_task_before_drop() {
+        let completed = Arc::new(AtomicUsize::new(0));
+        {
+            let pool = ThreadPool::new(POOL_WORKERS);
+            for _ in 0..200 {
+                let completed = Arc::clone(&completed);
+                pool.execute(move || {
+                    completed.fetch_add(1, Ordering::Relaxed);
+                });
+            }
+        }
+        assert_eq!(completed.load(Ordering::Relaxed), 200);
+    }
+
+    #[test]
+    fn spawn_vs_pool_completes_every_task_with_both_strategies() {
+        spawn_vs_pool(200);
+        spawn_vs_pool(0);
+    }
+}
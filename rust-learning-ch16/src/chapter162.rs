@@ -58,6 +58,8 @@ use std::sync::mpsc;
 
 use std::thread;
 
+// O Exemplo 16-7 ainda não usa rx; ele só será lido no Exemplo 16-8.
+#[allow(unused_variables)]
 pub fn move_channel() {
     let (tx, rx) = mpsc::channel();
 
@@ -89,4 +91,2067 @@ pub fn recover_channel() {
 
     let received = rx.recv().unwrap();
     println!("Got: {received}");
-}
\ No newline at end of file
+}
+
+// Propagando Erros Através de um Pipeline de Canais
+
+// Um canal pode transportar qualquer tipo que seja Send, inclusive Result<T, E>. Isso permite montar
+// um pipeline em que cada estágio roda na sua própria thread e repassa adiante tanto os valores
+// válidos quanto os erros. No exemplo abaixo, o primeiro estágio rejeita números negativos, o
+// segundo dobra os valores válidos, e a thread principal interrompe a coleta assim que encontra o
+// primeiro Err, devolvendo-o ao chamador.
+
+pub fn fallible_pipeline(input: Vec<i32>) -> Result<Vec<i32>, String> {
+    let (validate_tx, validate_rx) = mpsc::channel::<Result<i32, String>>();
+    let (double_tx, double_rx) = mpsc::channel::<Result<i32, String>>();
+
+    thread::spawn(move || {
+        for x in input {
+            let item = if x < 0 {
+                Err(format!("valor negativo: {x}"))
+            } else {
+                Ok(x)
+            };
+            let is_err = item.is_err();
+            if validate_tx.send(item).is_err() || is_err {
+                break;
+            }
+        }
+    });
+
+    thread::spawn(move || {
+        for item in validate_rx {
+            let is_err = item.is_err();
+            if double_tx.send(item.map(|x| x * 2)).is_err() || is_err {
+                break;
+            }
+        }
+    });
+
+    let mut output = Vec::new();
+    for item in double_rx {
+        output.push(item?);
+    }
+    Ok(output)
+}
+
+// Como cada estágio para de enviar depois de repassar um erro, os transmissores são descartados e o
+// laço for da thread principal termina. O operador ? faz o curto-circuito: o primeiro Err recebido
+// é devolvido imediatamente e o restante do pipeline é encerrado.
+
+// Confirmação de Mensagens com Entrega "Pelo Menos Uma Vez"
+
+// Um canal da biblioteca padrão nunca perde mensagens, mas em sistemas distribuídos reais uma
+// mensagem ou sua confirmação pode se perder no caminho. Para simular isso, usamos dois canais: um
+// para as mensagens e outro, no sentido contrário, para as confirmações (acks). O consumidor
+// "esquece" de confirmar uma das mensagens, e o produtor reenvia tudo o que não foi confirmado
+// dentro do tempo limite. O retorno é o total de envios, incluindo os reenvios.
+
+use std::collections::HashSet;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+
+pub fn at_least_once(messages: usize) -> usize {
+    let (msg_tx, msg_rx) = mpsc::channel::<usize>();
+    let (ack_tx, ack_rx) = mpsc::channel::<usize>();
+    let dropped_ack = messages / 2;
+
+    let consumer = thread::spawn(move || {
+        let mut already_dropped = false;
+        for id in msg_rx {
+            if id == dropped_ack && !already_dropped {
+                already_dropped = true;
+                continue;
+            }
+            if ack_tx.send(id).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut pending: HashSet<usize> = (0..messages).collect();
+    let mut sends = 0;
+    for id in 0..messages {
+        msg_tx.send(id).unwrap();
+        sends += 1;
+    }
+
+    while !pending.is_empty() {
+        match ack_rx.recv_timeout(Duration::from_millis(50)) {
+            Ok(id) => {
+                pending.remove(&id);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                for &id in &pending {
+                    msg_tx.send(id).unwrap();
+                    sends += 1;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    drop(msg_tx);
+    consumer.join().unwrap();
+    sends
+}
+
+// O consumidor pode acabar processando a mesma mensagem mais de uma vez, por isso esse modelo é
+// chamado de "pelo menos uma vez": nada se perde, mas duplicatas são possíveis.
+
+// Tratando o Receptor como um Iterador
+
+// Um Receiver pode ser convertido em iterador com into_iter: cada chamada a next bloqueia em recv
+// e a iteração termina quando todos os transmissores forem descartados. Com isso, podemos usar os
+// adaptadores de iteradores (map, filter, etc.) diretamente sobre as mensagens que chegam.
+
+pub fn receiver_iter<T: Send + 'static>(rx: mpsc::Receiver<T>) -> impl Iterator<Item = T> {
+    rx.into_iter()
+}
+
+pub fn stream_transform(input: Vec<i32>) -> Vec<i32> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for x in input {
+            tx.send(x).unwrap();
+        }
+    });
+
+    receiver_iter(rx)
+        .filter(|x| x % 2 == 0)
+        .map(|x| x * 3)
+        .collect()
+}
+
+// Broadcast com Histórico para Assinantes Atrasados
+
+// Um canal mpsc entrega cada mensagem a um único receptor. Para entregar a mesma mensagem a vários
+// assinantes, criamos um canal por assinante e clonamos cada valor enviado. O Broadcaster abaixo
+// também guarda o histórico de tudo o que já foi enviado: quem chama subscribe depois dos envios
+// recebe primeiro as mensagens antigas e, em seguida, as novas.
+
+use std::sync::{Arc, Mutex};
+
+struct BroadcasterState<T> {
+    history: Vec<T>,
+    subscribers: Vec<mpsc::Sender<T>>,
+}
+
+pub struct Broadcaster<T: Clone> {
+    state: Mutex<BroadcasterState<T>>,
+}
+
+impl<T: Clone> Broadcaster<T> {
+    pub fn new() -> Broadcaster<T> {
+        Broadcaster {
+            state: Mutex::new(BroadcasterState {
+                history: Vec::new(),
+                subscribers: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn send(&self, value: T) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .subscribers
+            .retain(|subscriber| subscriber.send(value.clone()).is_ok());
+        state.history.push(value);
+    }
+
+    pub fn subscribe(&self) -> mpsc::Receiver<T> {
+        let (tx, rx) = mpsc::channel();
+        let mut state = self.state.lock().unwrap();
+        for value in &state.history {
+            tx.send(value.clone()).unwrap();
+        }
+        state.subscribers.push(tx);
+        rx
+    }
+}
+
+impl<T: Clone> Default for Broadcaster<T> {
+    fn default() -> Broadcaster<T> {
+        Broadcaster::new()
+    }
+}
+
+pub fn broadcast_replay() -> Vec<i32> {
+    let broadcaster = Arc::new(Broadcaster::new());
+
+    let early = Arc::clone(&broadcaster);
+    thread::spawn(move || {
+        for i in 1..=3 {
+            early.send(i);
+        }
+    })
+    .join()
+    .unwrap();
+
+    let late_rx = broadcaster.subscribe();
+
+    let producer = Arc::clone(&broadcaster);
+    thread::spawn(move || {
+        for i in 4..=5 {
+            producer.send(i);
+        }
+    })
+    .join()
+    .unwrap();
+
+    drop(broadcaster);
+    late_rx.iter().collect()
+}
+
+// O histórico e a lista de assinantes ficam sob o mesmo Mutex, então um assinante nunca perde uma
+// mensagem enviada entre a reprodução do histórico e o seu registro. Assinantes cujo receptor foi
+// descartado são removidos no próximo envio.
+
+// Medindo a Latência de Cada Mensagem
+
+// Além de saber quantas mensagens um canal entrega por segundo, muitas vezes queremos saber quanto
+// tempo cada mensagem leva do envio ao recebimento. Para isso, o produtor carimba cada mensagem com
+// um Instant no momento do envio, e o consumidor calcula quanto tempo passou quando a recebe.
+
+use std::time::Instant;
+
+pub fn channel_latency(count: usize) -> Vec<Duration> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for _ in 0..count {
+            tx.send(Instant::now()).unwrap();
+        }
+    });
+
+    rx.iter().map(|sent| sent.elapsed()).collect()
+}
+
+// Instant é monotônico, então a diferença nunca é negativa. As primeiras mensagens costumam ter
+// latência maior porque ficam na fila enquanto o consumidor ainda está sendo agendado.
+
+// Transmitindo Resultados Parciais de um Cálculo Longo
+
+// Em vez de esperar um cálculo demorado terminar para devolver tudo de uma vez, a thread
+// trabalhadora pode enviar cada resultado assim que o encontra. Quem recebe já pode consumir os
+// primeiros valores enquanto o restante ainda está sendo calculado.
+
+pub fn streaming_primes(limit: u64) -> Vec<u64> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for n in 2..=limit {
+            if (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0) {
+                tx.send(n).unwrap();
+            }
+        }
+    });
+
+    let mut primes = Vec::new();
+    for prime in rx {
+        primes.push(prime);
+    }
+    primes
+}
+
+// Transferindo a Posse de um Valor pelo Canal
+
+// Quando enviamos um valor que não é Copy, como um Vec<i32>, a posse dele passa para quem recebe:
+// depois do send, a thread que enviou não pode mais usá-lo. Abaixo, a thread principal envia um
+// vetor para um worker, que o consome (calculando o tamanho) e devolve o resultado por um segundo
+// canal.
+
+pub fn transfer_ownership() -> usize {
+    let (data_tx, data_rx) = mpsc::channel::<Vec<i32>>();
+    let (len_tx, len_rx) = mpsc::channel();
+
+    let worker = thread::spawn(move || {
+        let data = data_rx.recv().unwrap();
+        len_tx.send(data.len()).unwrap();
+    });
+
+    let data = vec![1, 2, 3, 4, 5];
+    data_tx.send(data).unwrap();
+    // println!("{:?}", data); // erro: borrow of moved value: `data`
+
+    let len = len_rx.recv().unwrap();
+    worker.join().unwrap();
+    len
+}
+
+// A linha comentada não compila: o vetor foi movido para dentro do canal no send, exatamente como
+// aconteceria ao passá-lo por valor para uma função.
+
+// Coletando Resultados Até um Prazo Final
+
+// Nem sempre vale a pena esperar todas as threads terminarem. Aqui cada tarefa dorme pelo número de
+// milissegundos indicado e depois envia seu valor. A thread principal recebe com recv_timeout,
+// usando como limite o tempo que ainda resta até o prazo global; o que chegar depois disso é
+// descartado.
+
+pub fn bounded_time_collect(tasks: Vec<u64>, deadline_ms: u64) -> Vec<u64> {
+    let (tx, rx) = mpsc::channel();
+    let deadline = Instant::now() + Duration::from_millis(deadline_ms);
+
+    for ms in tasks {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(ms));
+            let _ = tx.send(ms);
+        });
+    }
+    drop(tx);
+
+    let mut collected = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok(value) => collected.push(value),
+            Err(_) => break,
+        }
+    }
+    collected
+}
+
+// As tarefas atrasadas continuam rodando até acordar, mas o send delas falha porque o receptor já
+// foi descartado; por isso ignoramos o resultado com let _ em vez de chamar unwrap.
+
+// Ping-Pong Entre Duas Threads
+
+// Um microbenchmark clássico de canais é o ping-pong: duas threads trocam um "token" de um lado
+// para o outro por dois canais, um em cada sentido. O tempo total dividido pelo número de rodadas
+// dá uma ideia da latência de ida e volta. Cada thread conta em um AtomicUsize quantas trocas
+// completou, e a função confere que ambas fizeram exatamente o número de rodadas pedido.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+pub fn ping_pong(rounds: usize) -> Duration {
+    let (ping_tx, ping_rx) = mpsc::channel::<usize>();
+    let (pong_tx, pong_rx) = mpsc::channel::<usize>();
+    let pings = Arc::new(AtomicUsize::new(0));
+    let pongs = Arc::new(AtomicUsize::new(0));
+
+    let start = Instant::now();
+
+    let ponger = {
+        let pongs = Arc::clone(&pongs);
+        thread::spawn(move || {
+            for token in ping_rx {
+                pongs.fetch_add(1, Ordering::SeqCst);
+                pong_tx.send(token).unwrap();
+            }
+        })
+    };
+
+    let pinger = {
+        let pings = Arc::clone(&pings);
+        thread::spawn(move || {
+            for round in 0..rounds {
+                ping_tx.send(round).unwrap();
+                pong_rx.recv().unwrap();
+                pings.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+    };
+
+    pinger.join().unwrap();
+    ponger.join().unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(pings.load(Ordering::SeqCst), rounds);
+    assert_eq!(pongs.load(Ordering::SeqCst), rounds);
+    elapsed
+}
+
+// Quando o pinger termina, ele descarta ping_tx; isso encerra o laço for do ponger, que então
+// também termina e pode ser aguardado com join.
+
+// Coletando Resultados em um HashMap Indexado
+
+// Quando várias threads enviam resultados pelo mesmo canal, eles chegam na ordem em que as threads
+// terminam, não na ordem em que foram criadas. Se cada thread enviar junto o seu índice, o receptor
+// pode guardar tudo em um HashMap e recuperar qualquer resultado pela chave, sem se preocupar com a
+// ordem de chegada.
+
+use std::collections::HashMap;
+
+pub fn indexed_results(n: usize) -> HashMap<usize, u64> {
+    let (tx, rx) = mpsc::channel();
+
+    for index in 0..n {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let result = (index * index) as u64;
+            tx.send((index, result)).unwrap();
+        });
+    }
+    drop(tx);
+
+    rx.iter().collect()
+}
+
+// Vários Consumidores com um Receptor Compartilhado
+
+// O mpsc permite muitos produtores, mas apenas um consumidor: Receiver não implementa Clone. Para
+// que várias threads puxem trabalho da mesma fila, envolvemos o receptor em um Arc<Mutex<...>>.
+// Cada worker trava o Mutex só pelo tempo de chamar recv, pega um job e libera o bloqueio antes de
+// processá-lo, deixando os outros workers livres para pegar o próximo. O retorno é quantos jobs
+// cada worker processou.
+
+pub fn shared_consumer(workers: usize, jobs: usize) -> Vec<usize> {
+    let (tx, rx) = mpsc::channel::<usize>();
+    let rx = Arc::new(Mutex::new(rx));
+
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let rx = Arc::clone(&rx);
+            thread::spawn(move || {
+                let mut processed = 0;
+                loop {
+                    let job = rx.lock().unwrap().recv();
+                    match job {
+                        Ok(_) => {
+                            processed += 1;
+                            thread::sleep(Duration::from_micros(100));
+                        }
+                        Err(_) => break,
+                    }
+                }
+                processed
+            })
+        })
+        .collect();
+
+    for job in 0..jobs {
+        tx.send(job).unwrap();
+    }
+    drop(tx);
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+// Note que o resultado de recv é guardado em job antes do match. Assim o MutexGuard temporário é
+// descartado ao final da instrução let, e o bloqueio não fica preso enquanto o job é processado.
+
+// Agrupando Envios em Rajadas em Lotes por Janela de Tempo
+
+// Quando mensagens chegam em rajadas, processá-las uma a uma pode ser caro. Em vez disso, o
+// consumidor pode juntar tudo o que chegar dentro de uma janela de tempo em um único lote. Abaixo,
+// o produtor envia os valores em rajadas de cinco, com uma pausa entre elas. O consumidor bloqueia
+// em recv esperando o primeiro valor de um lote e, a partir dele, usa recv_timeout para recolher
+// tudo o que chegar até o fim da janela de window_ms.
+
+pub fn coalesce(window_ms: u64, total: usize) -> Vec<Vec<i32>> {
+    const BURST: usize = 5;
+    let window = Duration::from_millis(window_ms);
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for (i, value) in (0..total as i32).enumerate() {
+            if i > 0 && i % BURST == 0 {
+                thread::sleep(window * 3);
+            }
+            tx.send(value).unwrap();
+        }
+    });
+
+    let mut batches = Vec::new();
+    while let Ok(first) = rx.recv() {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + window;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match rx.recv_timeout(remaining) {
+                Ok(value) => batch.push(value),
+                Err(_) => break,
+            }
+        }
+        batches.push(batch);
+    }
+    batches
+}
+
+// Quando o produtor termina, o transmissor é descartado: recv_timeout retorna Disconnected, o lote
+// atual é fechado e o próximo recv também falha, encerrando o laço externo.
+
+// Descartando o Transmissor para Encerrar o Laço do Receptor
+
+// O laço for v in rx só termina quando todos os transmissores do canal forem descartados. Uma
+// armadilha comum é clonar tx para as threads produtoras e esquecer que o tx original continua
+// vivo na thread principal: o produtor termina, mas o laço fica esperando para sempre por uma
+// mensagem que nunca virá. A solução é chamar drop(tx) explicitamente antes do laço.
+
+pub fn explicit_drop_to_close() -> Vec<i32> {
+    let (tx, rx) = mpsc::channel();
+
+    let producer_tx = tx.clone();
+    thread::spawn(move || {
+        for i in 1..=5 {
+            producer_tx.send(i).unwrap();
+        }
+    });
+
+    drop(tx);
+
+    let mut received = Vec::new();
+    for v in rx {
+        received.push(v);
+    }
+    received
+}
+
+// Sem o drop(tx), este exemplo nunca retornaria, mesmo depois de o produtor ter enviado todos os
+// valores e terminado.
+
+// Intercalando Fluxos Ordenados (K-Way Merge)
+
+// Imagine várias threads produzindo, cada uma, uma sequência já ordenada. Para obter uma única
+// sequência ordenada, não é preciso juntar tudo e ordenar de novo: basta olhar o primeiro elemento
+// ainda não consumido de cada canal e sempre tirar o menor. Guardamos essas "frentes" em um
+// BinaryHeap com Reverse, que funciona como uma fila de prioridade pelo menor valor; ao retirar um
+// valor, recebemos o próximo do mesmo canal e o colocamos no heap.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub fn merge_sorted_streams(streams: Vec<Vec<i32>>) -> Vec<i32> {
+    let receivers: Vec<mpsc::Receiver<i32>> = streams
+        .into_iter()
+        .map(|stream| {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                for value in stream {
+                    tx.send(value).unwrap();
+                }
+            });
+            rx
+        })
+        .collect();
+
+    let mut fronts = BinaryHeap::new();
+    for (index, rx) in receivers.iter().enumerate() {
+        if let Ok(value) = rx.recv() {
+            fronts.push(Reverse((value, index)));
+        }
+    }
+
+    let mut merged = Vec::new();
+    while let Some(Reverse((value, index))) = fronts.pop() {
+        merged.push(value);
+        if let Ok(next) = receivers[index].recv() {
+            fronts.push(Reverse((next, index)));
+        }
+    }
+    merged
+}
+
+// Quando um produtor termina, recv no seu canal retorna Err e aquele fluxo simplesmente deixa de
+// ter frente no heap. O merge acaba quando todos os fluxos se esgotarem.
+
+// Um Temporizador Cancelável e Reiniciável
+
+// Combinando uma thread com um canal de controle, podemos construir um temporizador. A thread
+// trabalhadora espera com recv_timeout pelo tempo que falta: se o tempo esgotar, o temporizador
+// disparou; se chegar um comando Reset, a contagem recomeça do zero; se chegar Cancel (ou se o
+// Timer for descartado e o canal fechar), ele termina sem disparar. wait aguarda a thread e
+// devolve true se o temporizador disparou e false se foi cancelado.
+
+enum TimerCommand {
+    Reset,
+    Cancel,
+}
+
+pub struct Timer {
+    control: mpsc::Sender<TimerCommand>,
+    handle: thread::JoinHandle<bool>,
+}
+
+impl Timer {
+    pub fn start(duration: Duration) -> Timer {
+        let (control, commands) = mpsc::channel();
+
+        let handle = thread::spawn(move || {
+            let mut deadline = Instant::now() + duration;
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match commands.recv_timeout(remaining) {
+                    Ok(TimerCommand::Reset) => deadline = Instant::now() + duration,
+                    Ok(TimerCommand::Cancel) | Err(RecvTimeoutError::Disconnected) => return false,
+                    Err(RecvTimeoutError::Timeout) => return true,
+                }
+            }
+        });
+
+        Timer { control, handle }
+    }
+
+    pub fn reset(&self) {
+        let _ = self.control.send(TimerCommand::Reset);
+    }
+
+    pub fn cancel(&self) {
+        let _ = self.control.send(TimerCommand::Cancel);
+    }
+
+    pub fn wait(self) -> bool {
+        let fired = self.handle.join().unwrap();
+        drop(self.control);
+        fired
+    }
+}
+
+pub fn timer_demo(fire: bool) -> bool {
+    let timer = Timer::start(Duration::from_millis(50));
+    thread::sleep(Duration::from_millis(10));
+    timer.reset();
+    if !fire {
+        timer.cancel();
+    }
+    timer.wait()
+}
+
+// Em wait, o transmissor de controle só é descartado depois do join. Se fosse descartado antes, a
+// thread veria o canal fechado e trataria isso como cancelamento.
+
+// Lidando com um sync_channel Cheio Usando try_send
+
+// mpsc::sync_channel cria um canal com capacidade limitada: quando o buffer está cheio, send
+// bloqueia até o consumidor liberar espaço. Se o produtor não puder ficar bloqueado, ele pode usar
+// try_send, que retorna TrySendError::Full na hora. Abaixo, o produtor tenta de novo algumas vezes,
+// dormindo um pouco entre as tentativas, e desiste da mensagem depois de retries tentativas
+// extras. O consumidor é propositalmente lento, e o retorno é quantas mensagens foram aceitas.
+
+use std::sync::mpsc::TrySendError;
+
+pub fn adaptive_send(capacity: usize, total: usize, retries: usize) -> usize {
+    let (tx, rx) = mpsc::sync_channel(capacity);
+
+    let consumer = thread::spawn(move || {
+        for _ in rx {
+            thread::sleep(Duration::from_millis(5));
+        }
+    });
+
+    let mut sent = 0;
+    for value in 0..total {
+        let mut message = value;
+        for _ in 0..=retries {
+            match tx.try_send(message) {
+                Ok(()) => {
+                    sent += 1;
+                    break;
+                }
+                Err(TrySendError::Full(returned)) => {
+                    message = returned;
+                    thread::sleep(Duration::from_millis(1));
+                }
+                Err(TrySendError::Disconnected(_)) => break,
+            }
+        }
+    }
+
+    drop(tx);
+    consumer.join().unwrap();
+    sent
+}
+
+// TrySendError::Full devolve a mensagem que não coube, então podemos reaproveitá-la na próxima
+// tentativa em vez de precisar de uma cópia.
+
+// Média Móvel Sobre um Fluxo de Valores
+
+// Um consumidor de canal pode manter estado entre mensagens. Aqui ele guarda os últimos window
+// valores recebidos em um VecDeque: a cada novo valor, empurra-o no final, descarta o mais antigo
+// se a janela passou do tamanho e emite a média dos valores que estão na janela.
+
+use std::collections::VecDeque;
+
+pub fn rolling_window(values: Vec<i32>, window: usize) -> Vec<f64> {
+    let window = window.max(1);
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for value in values {
+            tx.send(value).unwrap();
+        }
+    });
+
+    let mut recent = VecDeque::with_capacity(window);
+    let mut sum: i64 = 0;
+    let mut averages = Vec::new();
+
+    for value in rx {
+        recent.push_back(value);
+        sum += value as i64;
+        if recent.len() > window {
+            sum -= recent.pop_front().unwrap() as i64;
+        }
+        averages.push(sum as f64 / recent.len() as f64);
+    }
+    averages
+}
+
+// Manter a soma atualizada evita percorrer a janela inteira a cada valor: somamos o que entra e
+// subtraímos o que sai.
+
+// Map, Filter e Reduce em Estágios Paralelos
+
+// As três operações clássicas de processamento de dados podem virar estágios de um pipeline, cada
+// um com vários workers e ligados por canais. No map, cada worker eleva ao quadrado um pedaço dos
+// dados. No filter, os workers disputam o mesmo receptor (compartilhado com Arc<Mutex<...>>) e só
+// repassam os quadrados a partir de um limite. No reduce, cada worker soma o que consegue puxar e
+// envia a soma parcial, que a thread principal combina no resultado final.
+
+const SQUARE_THRESHOLD: i64 = 10;
+
+pub fn map_filter_reduce(data: Vec<i32>, workers: usize) -> i64 {
+    let workers = workers.max(1);
+    let (mapped_tx, mapped_rx) = mpsc::channel::<i64>();
+    let (filtered_tx, filtered_rx) = mpsc::channel::<i64>();
+    let (partial_tx, partial_rx) = mpsc::channel::<i64>();
+
+    let chunk_size = data.len().div_ceil(workers).max(1);
+    for chunk in data.chunks(chunk_size) {
+        let chunk = chunk.to_vec();
+        let mapped_tx = mapped_tx.clone();
+        thread::spawn(move || {
+            for x in chunk {
+                mapped_tx.send(x as i64 * x as i64).unwrap();
+            }
+        });
+    }
+    drop(mapped_tx);
+
+    let mapped_rx = Arc::new(Mutex::new(mapped_rx));
+    for _ in 0..workers {
+        let mapped_rx = Arc::clone(&mapped_rx);
+        let filtered_tx = filtered_tx.clone();
+        thread::spawn(move || loop {
+            let square = mapped_rx.lock().unwrap().recv();
+            match square {
+                Ok(square) if square >= SQUARE_THRESHOLD => filtered_tx.send(square).unwrap(),
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        });
+    }
+    drop(filtered_tx);
+
+    let filtered_rx = Arc::new(Mutex::new(filtered_rx));
+    for _ in 0..workers {
+        let filtered_rx = Arc::clone(&filtered_rx);
+        let partial_tx = partial_tx.clone();
+        thread::spawn(move || {
+            let mut partial = 0;
+            loop {
+                let square = filtered_rx.lock().unwrap().recv();
+                match square {
+                    Ok(square) => partial += square,
+                    Err(_) => break,
+                }
+            }
+            partial_tx.send(partial).unwrap();
+        });
+    }
+    drop(partial_tx);
+
+    partial_rx.iter().sum()
+}
+
+// Cada estágio descarta o seu transmissor original depois de distribuir os clones. Assim, quando
+// todos os workers de um estágio terminam, o canal seguinte se fecha e o próximo estágio também
+// termina, em cascata até a thread principal.
+
+// Mensagens que Carregam o Próprio Canal de Resposta
+
+// Quando um cliente precisa de uma resposta para cada pedido, um padrão idiomático é embutir um
+// transmissor de resposta dentro da própria mensagem. O servidor recebe a Query, calcula o
+// resultado e o envia pelo respond_to daquela query, sem precisar saber quem é o cliente nem manter
+// uma tabela de respostas pendentes.
+
+pub struct Query {
+    pub id: u32,
+    pub respond_to: mpsc::Sender<u32>,
+}
+
+pub fn query_server(queries: usize) -> Vec<u32> {
+    let (server_tx, server_rx) = mpsc::channel::<Query>();
+
+    let server = thread::spawn(move || {
+        for query in server_rx {
+            let _ = query.respond_to.send(query.id * 2);
+        }
+    });
+
+    let mut responses = Vec::with_capacity(queries);
+    for id in 0..queries as u32 {
+        let (respond_to, response_rx) = mpsc::channel();
+        server_tx.send(Query { id, respond_to }).unwrap();
+        responses.push(response_rx.recv().unwrap());
+    }
+
+    drop(server_tx);
+    server.join().unwrap();
+    responses
+}
+
+// Cada consulta tem seu próprio canal de resposta, então não há como uma resposta ser entregue ao
+// cliente errado.
+
+// Corrida: o Primeiro que Terminar Vence
+
+// Às vezes disparamos várias tentativas equivalentes (por exemplo, a mesma consulta em réplicas
+// diferentes) e só nos interessa a primeira resposta. Cada tarefa abaixo "trabalha" dormindo em
+// pequenos intervalos e, entre eles, consulta uma flag de cancelamento. A thread principal pega o
+// primeiro valor que chegar no canal e liga a flag, fazendo as demais tarefas desistirem.
+
+fn sleep_unless_aborted(ms: u64, abort: &AtomicBool) -> bool {
+    let deadline = Instant::now() + Duration::from_millis(ms);
+    while Instant::now() < deadline {
+        if abort.load(Ordering::SeqCst) {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    true
+}
+
+pub fn race_to_finish(tasks: Vec<u64>) -> u64 {
+    let abort = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+
+    let handles: Vec<_> = tasks
+        .into_iter()
+        .map(|ms| {
+            let abort = Arc::clone(&abort);
+            let tx = tx.clone();
+            thread::spawn(move || {
+                if sleep_unless_aborted(ms, &abort) {
+                    let _ = tx.send(ms);
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let winner = rx
+        .recv()
+        .expect("race_to_finish precisa de pelo menos uma tarefa");
+    abort.store(true, Ordering::SeqCst);
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    winner
+}
+
+// O join no final mostra que as tarefas perdedoras realmente terminam logo depois do sinal, em vez
+// de continuar rodando até completar o seu tempo.
+
+// Vazão por Worker para Analisar o Balanceamento de Carga
+
+// Com o receptor compartilhado de shared_consumer, os workers disputam os jobs: quem fica livre
+// primeiro pega o próximo. Isso já é uma forma de balanceamento de carga, pois um worker mais lento
+// simplesmente pega menos jobs. Olhar quantos jobs cada worker processou mostra como o trabalho se
+// distribuiu, e é a mesma intuição por trás do roubo de trabalho (work stealing) usado por
+// bibliotecas como o rayon.
+
+pub fn worker_throughput(workers: usize, total_jobs: usize) -> Vec<usize> {
+    shared_consumer(workers, total_jobs)
+}
+
+// Execute algumas vezes e compare: os totais por worker mudam de uma execução para outra, mas a
+// soma é sempre total_jobs.
+
+// Broadcast sem Cópias Usando Arc
+
+// Enviar um Vec<u8> grande para vários workers normalmente exigiria um clone por worker. Se os
+// workers só precisam ler os dados, podemos colocá-los em um Arc<Vec<u8>> e enviar um Arc::clone
+// para cada um: clonar o Arc só incrementa o contador de referências, e todos leem o mesmo buffer.
+// Enquanto os workers seguram seus clones, Arc::strong_count mostra quantas referências existem.
+
+pub fn zero_copy_broadcast(payload: Vec<u8>, receivers: usize) -> Vec<usize> {
+    let payload = Arc::new(payload);
+    let (result_tx, result_rx) = mpsc::channel();
+
+    let senders: Vec<mpsc::Sender<Arc<Vec<u8>>>> = (0..receivers)
+        .map(|_| {
+            let (tx, rx) = mpsc::channel::<Arc<Vec<u8>>>();
+            let result_tx = result_tx.clone();
+            thread::spawn(move || {
+                for shared in rx {
+                    result_tx
+                        .send((shared.len(), Arc::strong_count(&shared)))
+                        .unwrap();
+                }
+            });
+            tx
+        })
+        .collect();
+    drop(result_tx);
+
+    for tx in &senders {
+        tx.send(Arc::clone(&payload)).unwrap();
+    }
+    drop(senders);
+
+    result_rx
+        .iter()
+        .map(|(len, count)| {
+            assert!(count >= 2, "worker viu strong_count {count}");
+            len
+        })
+        .collect()
+}
+
+// Cada worker vê um strong_count de pelo menos 2: a referência da thread principal e a sua própria,
+// e a função confere isso antes de devolver os tamanhos.
+// Quando todos terminam, só resta a referência original, e o Vec é liberado uma única vez.
+
+// Controle de Fluxo por Créditos
+
+// Um sync_channel limita o buffer, mas outra forma de evitar que o produtor atropele o consumidor
+// é o controle de fluxo por créditos. O produtor começa com initial_credits créditos e gasta um a
+// cada mensagem enviada; quando os créditos acabam, ele espera. O consumidor devolve um crédito por
+// um canal no sentido contrário sempre que termina de processar uma mensagem. Assim, nunca há mais
+// de initial_credits mensagens pendentes.
+
+pub fn credit_flow(total: usize, initial_credits: usize) -> usize {
+    let (data_tx, data_rx) = mpsc::channel::<usize>();
+    let (credit_tx, credit_rx) = mpsc::channel::<()>();
+    let in_flight = Arc::new(AtomicUsize::new(0));
+
+    let consumer = {
+        let in_flight = Arc::clone(&in_flight);
+        thread::spawn(move || {
+            let mut delivered = 0;
+            for _ in data_rx {
+                thread::sleep(Duration::from_micros(200));
+                delivered += 1;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                let _ = credit_tx.send(());
+            }
+            delivered
+        })
+    };
+
+    let initial_credits = initial_credits.max(1);
+    let mut credits = initial_credits;
+    let mut max_in_flight = 0;
+    for message in 0..total {
+        if credits == 0 {
+            credit_rx.recv().unwrap();
+            credits += 1;
+        }
+        credits -= 1;
+        let pending = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight = max_in_flight.max(pending);
+        data_tx.send(message).unwrap();
+    }
+    drop(data_tx);
+
+    assert!(
+        max_in_flight <= initial_credits,
+        "{max_in_flight} mensagens pendentes com {initial_credits} créditos"
+    );
+    consumer.join().unwrap()
+}
+
+// O produtor só recupera um crédito depois que o consumidor decrementou in_flight, então o número de
+// mensagens pendentes nunca passa dos créditos iniciais; o assert no fim da função confere isso.
+
+// Relatório de Sucessos e Erros de um Lote
+
+// Quando um lote de entradas é processado em paralelo, algumas podem falhar sem que isso deva
+// interromper as outras. Cada worker envia pelo canal o índice da sua entrada junto com um Result,
+// e a thread principal monta um Report com o número de sucessos e as mensagens de erro. Ordenar
+// pelo índice antes de montar o relatório deixa as mensagens na mesma ordem da entrada.
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Report {
+    pub ok: usize,
+    pub errors: Vec<String>,
+}
+
+pub fn run_batch(inputs: Vec<i32>) -> Report {
+    let (tx, rx) = mpsc::channel();
+
+    for (index, input) in inputs.into_iter().enumerate() {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let result = if input < 0 {
+                Err(format!("entrada {index} inválida: {input}"))
+            } else {
+                Ok(input * 2)
+            };
+            tx.send((index, result)).unwrap();
+        });
+    }
+    drop(tx);
+
+    let mut results: Vec<(usize, Result<i32, String>)> = rx.iter().collect();
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut report = Report::default();
+    for (_, result) in results {
+        match result {
+            Ok(_) => report.ok += 1,
+            Err(e) => report.errors.push(e),
+        }
+    }
+    report
+}
+
+// Coletando os N Primeiros Resultados e Cancelando o Resto
+
+// Generalizando race_to_finish: em vez de só o primeiro, queremos os n primeiros resultados. A
+// thread principal recebe n valores do canal e então liga a flag de cancelamento; as tarefas que
+// ainda estão dormindo percebem a flag em sleep_unless_aborted e terminam sem enviar nada.
+
+pub fn first_n_results(tasks: Vec<u64>, n: usize) -> Vec<u64> {
+    let abort = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+
+    let handles: Vec<_> = tasks
+        .into_iter()
+        .map(|ms| {
+            let abort = Arc::clone(&abort);
+            let tx = tx.clone();
+            thread::spawn(move || {
+                if sleep_unless_aborted(ms, &abort) {
+                    let _ = tx.send(ms);
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let collected: Vec<u64> = rx.iter().take(n).collect();
+    abort.store(true, Ordering::SeqCst);
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    collected
+}
+
+// Se houver menos de n tarefas, rx.iter() termina quando todos os transmissores forem descartados
+// e simplesmente devolvemos o que chegou.
+
+// Pipeline com Buffers Limitados entre os Estágios
+
+// Em fallible_pipeline os estágios são ligados por mpsc::channel, que tem buffer ilimitado: se o
+// último estágio for lento, os anteriores continuam produzindo e as mensagens se acumulam na
+// memória. Trocando por sync_channel(buffer), cada estágio bloqueia no send quando o buffer do
+// próximo está cheio, e essa pressão (backpressure) se propaga até a fonte. Abaixo, a fonte envia
+// os valores, o segundo estágio os dobra e o terceiro, propositalmente lento, soma 1.
+
+pub fn bounded_pipeline(input: Vec<i32>, buffer: usize) -> Vec<i32> {
+    let (source_tx, source_rx) = mpsc::sync_channel::<i32>(buffer);
+    let (double_tx, double_rx) = mpsc::sync_channel::<i32>(buffer);
+    let (slow_tx, slow_rx) = mpsc::sync_channel::<i32>(buffer);
+    let produced = Arc::new(AtomicUsize::new(0));
+    let consumed = Arc::new(AtomicUsize::new(0));
+    let max_ahead = Arc::new(AtomicUsize::new(0));
+
+    {
+        let produced = Arc::clone(&produced);
+        let consumed = Arc::clone(&consumed);
+        let max_ahead = Arc::clone(&max_ahead);
+        thread::spawn(move || {
+            for x in input {
+                let ahead =
+                    produced.fetch_add(1, Ordering::SeqCst) + 1 - consumed.load(Ordering::SeqCst);
+                max_ahead.fetch_max(ahead, Ordering::SeqCst);
+                source_tx.send(x).unwrap();
+            }
+        });
+    }
+
+    thread::spawn(move || {
+        for x in source_rx {
+            double_tx.send(x * 2).unwrap();
+        }
+    });
+
+    {
+        let consumed = Arc::clone(&consumed);
+        thread::spawn(move || {
+            for x in double_rx {
+                consumed.fetch_add(1, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(1));
+                slow_tx.send(x + 1).unwrap();
+            }
+        });
+    }
+
+    let output = slow_rx.iter().collect();
+    let max_ahead = max_ahead.load(Ordering::SeqCst);
+    assert!(
+        max_ahead <= 2 * buffer + 3,
+        "fonte ficou {max_ahead} itens à frente"
+    );
+    output
+}
+
+// A fonte nunca fica mais do que 2 * buffer + 3 itens à frente do estágio lento: no máximo buffer
+// itens em cada um dos dois canais, mais um item na mão da fonte, outro na mão do estágio do meio e
+// um que o estágio lento acabou de receber, mas ainda não contou. Com mpsc::channel, essa diferença
+// poderia crescer até o tamanho da entrada inteira; o assert antes do retorno confere esse limite.
+
+// Máquina de Estados Dirigida por um Canal
+
+// Um padrão comum é deixar o estado sob responsabilidade exclusiva de uma thread, que o altera
+// conforme os comandos recebidos por um canal. Como só essa thread toca no estado, não é preciso
+// Mutex: as outras threads apenas enviam comandos. Abaixo, o worker começa em Idle, vai para
+// Running com "start", conta os "tick" recebidos e termina em Done com "stop". Comandos que não
+// fazem sentido no estado atual são ignorados. O estado final volta pelo JoinHandle.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum State {
+    Idle,
+    Running(u32),
+    Done,
+}
+
+pub fn state_machine(commands: Vec<&str>) -> State {
+    let (tx, rx) = mpsc::channel::<String>();
+
+    let worker = thread::spawn(move || {
+        let mut state = State::Idle;
+        for command in rx {
+            state = match (state, command.as_str()) {
+                (State::Idle, "start") => State::Running(0),
+                (State::Running(ticks), "tick") => State::Running(ticks + 1),
+                (State::Running(_), "stop") => State::Done,
+                (state, _) => state,
+            };
+        }
+        state
+    });
+
+    for command in commands {
+        tx.send(command.to_string()).unwrap();
+    }
+    drop(tx);
+
+    worker.join().unwrap()
+}
+
+// Os comandos são convertidos em String antes do envio porque um &str emprestado do chamador não
+// vive o suficiente para atravessar para outra thread.
+
+// Encerrando o Fluxo com um Valor Sentinela
+
+// Até aqui, o receptor sabia que as mensagens tinham acabado porque todos os transmissores eram
+// descartados. Outra forma é um protocolo "em banda": o produtor envia um valor especial, o
+// sentinela, para marcar o fim do fluxo. Aqui o sentinela é -1, e o consumidor para de ler assim
+// que o recebe, mesmo que o transmissor continue vivo.
+
+const SENTINEL: i32 = -1;
+
+pub fn sentinel_drain() -> Vec<i32> {
+    let (tx, rx) = mpsc::channel();
+
+    let producer = thread::spawn(move || {
+        for value in 1..=5 {
+            tx.send(value).unwrap();
+        }
+        tx.send(SENTINEL).unwrap();
+        tx
+    });
+
+    let received = rx.iter().take_while(|&value| value != SENTINEL).collect();
+
+    drop(producer.join().unwrap());
+    received
+}
+
+// O produtor devolve o tx pelo join justamente para mostrar que o laço terminou por causa do
+// sentinela, e não porque o canal foi fechado. A desvantagem desse protocolo é que o valor
+// sentinela não pode aparecer entre os dados normais.
+
+// Percentis de Tempo de Execução das Tarefas
+
+// A média esconde os casos lentos. Para entender o comportamento de um conjunto de tarefas, é
+// comum olhar percentis: o p50 (mediana) diz quanto leva uma tarefa típica, enquanto o p90 e o p99
+// mostram as mais demoradas. Cada worker abaixo mede o próprio tempo de execução e o envia pelo
+// canal; a thread principal ordena as durações e escolhe os percentis pelo método do posto mais
+// próximo (nearest rank).
+
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+pub fn timing_percentiles(tasks: Vec<u64>) -> (Duration, Duration, Duration) {
+    let (tx, rx) = mpsc::channel();
+
+    for ms in tasks {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let start = Instant::now();
+            thread::sleep(Duration::from_millis(ms));
+            tx.send(start.elapsed()).unwrap();
+        });
+    }
+    drop(tx);
+
+    let mut durations: Vec<Duration> = rx.iter().collect();
+    durations.sort();
+
+    (
+        percentile(&durations, 50),
+        percentile(&durations, 90),
+        percentile(&durations, 99),
+    )
+}
+
+// Assinatura Cancelável
+
+// Quando alguém assina um fluxo de valores, precisa de um jeito de dizer "não quero mais". A função
+// subscribe abaixo inicia um produtor e devolve duas coisas: o receptor dos valores e uma
+// Subscription. Chamar cancel liga uma flag que o produtor consulta a cada envio e espera, com
+// join, que ele termine. Implementando Drop, a assinatura também é cancelada automaticamente
+// quando sai de escopo, no estilo RAII.
+
+pub struct Subscription {
+    stop: Arc<AtomicBool>,
+    producer: Option<thread::JoinHandle<()>>,
+}
+
+impl Subscription {
+    pub fn cancel(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(producer) = self.producer.take() {
+            producer.join().unwrap();
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+pub fn subscribe() -> (Subscription, mpsc::Receiver<i32>) {
+    let (tx, rx) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let producer = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut value = 0;
+            while !stop.load(Ordering::SeqCst) {
+                if tx.send(value).is_err() {
+                    break;
+                }
+                value += 1;
+                thread::sleep(Duration::from_millis(1));
+            }
+        })
+    };
+
+    let subscription = Subscription {
+        stop,
+        producer: Some(producer),
+    };
+    (subscription, rx)
+}
+
+// Depois do cancelamento, o produtor já terminou e descartou o seu transmissor: o receptor entrega
+// o que ainda estava no buffer e, em seguida, recv passa a retornar Err.
+
+// Detectando Entregas Duplicadas
+
+// Em sistemas com entrega "pelo menos uma vez", como em at_least_once, o consumidor pode receber a
+// mesma mensagem mais de uma vez. Para que isso não cause efeitos repetidos, o consumidor precisa
+// ser idempotente: ele guarda os ids já vistos em um HashSet e ignora os repetidos. Abaixo, um
+// produtor "com defeito" reenvia alguns ids quando send_twice é true, e o consumidor conta quantas
+// duplicatas descartou.
+
+pub fn detect_duplicates(send_twice: bool) -> usize {
+    let (tx, rx) = mpsc::channel::<u32>();
+
+    thread::spawn(move || {
+        for id in 0..10 {
+            tx.send(id).unwrap();
+            if send_twice && id % 3 == 0 {
+                tx.send(id).unwrap();
+            }
+        }
+    });
+
+    let mut seen = HashSet::new();
+    let mut duplicates = 0;
+    for id in rx {
+        if !seen.insert(id) {
+            duplicates += 1;
+        }
+    }
+    duplicates
+}
+
+// HashSet::insert retorna false quando o valor já estava no conjunto, o que serve exatamente como
+// teste de "já processei esta mensagem?".
+
+// Encerramento Gracioso de um Fan-Out
+
+// Para desligar um grupo de workers de forma limpa, três passos precisam acontecer em ordem: o
+// coordenador distribui todos os jobs, descarta os transmissores para que o laço for job in rx de
+// cada worker termine e, por fim, aguarda cada worker com join. O valor devolvido pelo join funciona
+// como confirmação: cada worker informa quantos jobs processou antes de sair.
+
+pub fn fan_out_shutdown(workers: usize, jobs: usize) -> usize {
+    let workers = workers.max(1);
+    let mut senders = Vec::with_capacity(workers);
+    let mut handles = Vec::with_capacity(workers);
+
+    for _ in 0..workers {
+        let (tx, rx) = mpsc::channel::<usize>();
+        senders.push(tx);
+        handles.push(thread::spawn(move || {
+            let mut processed = 0;
+            for _job in rx {
+                processed += 1;
+            }
+            processed
+        }));
+    }
+
+    for job in 0..jobs {
+        senders[job % workers].send(job).unwrap();
+    }
+    drop(senders);
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .sum()
+}
+
+// Se o drop(senders) fosse esquecido, os workers ficariam esperando mais jobs para sempre e o join
+// nunca retornaria.
+
+// Distinguindo Erros de Envio e de Recebimento
+
+// Um canal pode falhar de duas formas. send retorna Err (um SendError com o valor devolvido) quando
+// o receptor já foi descartado: não há mais ninguém para receber. recv retorna Err (um RecvError)
+// quando todos os transmissores foram descartados e o canal está vazio: nenhum valor vai chegar.
+// Abaixo, provocamos uma das duas situações e devolvemos a variante correspondente.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChannelError {
+    Send,
+    Recv,
+}
+
+pub fn classify_channel_error(drop_receiver: bool) -> ChannelError {
+    let (tx, rx) = mpsc::channel::<i32>();
+
+    if drop_receiver {
+        drop(rx);
+        let handle = thread::spawn(move || tx.send(1));
+        match handle.join().unwrap() {
+            Err(_) => ChannelError::Send,
+            Ok(()) => unreachable!("o receptor foi descartado antes do envio"),
+        }
+    } else {
+        let handle = thread::spawn(move || drop(tx));
+        handle.join().unwrap();
+        match rx.recv() {
+            Err(_) => ChannelError::Recv,
+            Ok(_) => unreachable!("nenhum valor foi enviado"),
+        }
+    }
+}
+
+// O SendError carrega o valor que não pôde ser entregue, para que quem enviou não o perca; o
+// RecvError não carrega nada, porque não havia valor algum.
+
+// Normalização em Duas Passadas Paralelas
+
+// Alguns algoritmos precisam de uma informação global antes de processar cada elemento. Para
+// normalizar valores no intervalo [0, 1], por exemplo, é preciso conhecer o mínimo e o máximo do
+// conjunto inteiro. Na primeira passada, cada worker calcula o mínimo e o máximo do seu pedaço e
+// envia o par pelo canal; a thread principal combina os parciais. Na segunda passada, cada worker
+// normaliza o seu pedaço usando os valores globais e devolve o resultado junto com o índice do
+// pedaço, para que a ordem original seja restaurada.
+
+pub fn two_pass_normalize(data: Vec<f64>, workers: usize) -> Vec<f64> {
+    if data.is_empty() {
+        return data;
+    }
+    let chunk_size = data.len().div_ceil(workers.max(1)).max(1);
+
+    let (tx, rx) = mpsc::channel::<(f64, f64)>();
+    thread::scope(|s| {
+        for chunk in data.chunks(chunk_size) {
+            let tx = tx.clone();
+            s.spawn(move || {
+                let min = chunk.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = chunk.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                tx.send((min, max)).unwrap();
+            });
+        }
+    });
+    drop(tx);
+    let (min, max) = rx.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), (lo, hi)| (min.min(lo), max.max(hi)),
+    );
+    let range = max - min;
+
+    let (tx, rx) = mpsc::channel::<(usize, Vec<f64>)>();
+    thread::scope(|s| {
+        for (index, chunk) in data.chunks(chunk_size).enumerate() {
+            let tx = tx.clone();
+            s.spawn(move || {
+                let normalized = chunk
+                    .iter()
+                    .map(|x| if range > 0.0 { (x - min) / range } else { 0.0 })
+                    .collect();
+                tx.send((index, normalized)).unwrap();
+            });
+        }
+    });
+    drop(tx);
+
+    let mut pieces: Vec<(usize, Vec<f64>)> = rx.iter().collect();
+    pieces.sort_by_key(|(index, _)| *index);
+    pieces.into_iter().flat_map(|(_, piece)| piece).collect()
+}
+
+// Se todos os valores forem iguais, o intervalo é zero e a divisão não faria sentido; nesse caso
+// todos viram 0.0. A barreira entre as duas passadas é o próprio fim do primeiro thread::scope.
+
+// Agrupando Resultados por uma Chave Calculada
+
+// Uma forma comum de agregação é particionar os dados conforme uma chave. Aqui cada worker recebe
+// um pedaço do vetor, calcula a paridade de cada elemento e envia o par (é_par, valor) pelo canal.
+// O coletor só precisa olhar a chave para decidir em qual grupo guardar o valor. Como os pares
+// chegam em ordem imprevisível, cada grupo é ordenado no fim.
+
+pub fn group_by_parity(data: Vec<i32>, workers: usize) -> (Vec<i32>, Vec<i32>) {
+    let chunk_size = data.len().div_ceil(workers.max(1)).max(1);
+    let (tx, rx) = mpsc::channel::<(bool, i32)>();
+
+    for chunk in data.chunks(chunk_size) {
+        let chunk = chunk.to_vec();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for value in chunk {
+                tx.send((value % 2 == 0, value)).unwrap();
+            }
+        });
+    }
+    drop(tx);
+
+    let mut evens = Vec::new();
+    let mut odds = Vec::new();
+    for (is_even, value) in rx {
+        if is_even {
+            evens.push(value);
+        } else {
+            odds.push(value);
+        }
+    }
+    evens.sort();
+    odds.sort();
+    (evens, odds)
+}
+
+// Com mais de duas chaves, o coletor usaria um HashMap da chave para o Vec do grupo, exatamente
+// como um GROUP BY em SQL.
+
+// Cancelando um recv que Bloquearia para Sempre
+
+// recv bloqueia até chegar um valor ou todos os transmissores sumirem, e não há como interrompê-lo
+// de fora. Se o produtor continuar vivo sem nunca enviar nada, o consumidor fica preso. A saída é
+// trocar recv por recv_timeout com um intervalo curto e, a cada timeout, conferir uma flag de
+// cancelamento. Abaixo, o produtor segura o transmissor sem enviar, e outra thread liga a flag
+// depois de cancel_after_ms.
+
+pub fn interruptible_recv(cancel_after_ms: u64) -> Option<i32> {
+    let (tx, rx) = mpsc::channel::<i32>();
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    let producer = {
+        let cancelled = Arc::clone(&cancelled);
+        thread::spawn(move || {
+            let _tx = tx;
+            while !cancelled.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(5));
+            }
+        })
+    };
+
+    let canceller = {
+        let cancelled = Arc::clone(&cancelled);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(cancel_after_ms));
+            cancelled.store(true, Ordering::SeqCst);
+        })
+    };
+
+    let result = loop {
+        match rx.recv_timeout(Duration::from_millis(10)) {
+            Ok(value) => break Some(value),
+            Err(RecvTimeoutError::Timeout) => {
+                if cancelled.load(Ordering::SeqCst) {
+                    break None;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break None,
+        }
+    };
+
+    canceller.join().unwrap();
+    producer.join().unwrap();
+    result
+}
+
+// O intervalo do recv_timeout define quanto tempo o cancelamento pode levar para ser percebido:
+// menor responde mais rápido, mas acorda a thread com mais frequência.
+
+// Pipeline de Quadros com Orçamento de Latência
+
+// Processamento em tempo real, como áudio ou vídeo, costuma ser organizado como uma cadeia de
+// estágios: cada quadro passa por todos eles, em ordem, e cada estágio roda na sua própria thread.
+// Canais limitados (sync_channel) entre os estágios impedem que um estágio rápido acumule quadros
+// demais na frente de um lento. Cada quadro carrega o Instant em que entrou no pipeline, e o
+// coletor no fim registra quanto tempo, em microssegundos, ele levou para atravessar tudo.
+
+const FRAME_STAGE_DELAY_MS: u64 = 1;
+
+pub fn frame_pipeline(frames: usize, stages: usize) -> Vec<u64> {
+    let (source_tx, mut rx) = mpsc::sync_channel::<(Instant, u64)>(2);
+
+    let mut handles = Vec::with_capacity(stages);
+    for stage in 0..stages {
+        let (tx, next_rx) = mpsc::sync_channel::<(Instant, u64)>(2);
+        let input = rx;
+        handles.push(thread::spawn(move || {
+            for (started, sample) in input {
+                thread::sleep(Duration::from_millis(FRAME_STAGE_DELAY_MS));
+                let processed = sample.wrapping_mul(31).wrapping_add(stage as u64);
+                if tx.send((started, processed)).is_err() {
+                    break;
+                }
+            }
+        }));
+        rx = next_rx;
+    }
+
+    let producer = thread::spawn(move || {
+        for frame in 0..frames {
+            source_tx.send((Instant::now(), frame as u64)).unwrap();
+        }
+    });
+
+    let latencies: Vec<u64> = rx
+        .iter()
+        .map(|(started, _)| started.elapsed().as_micros() as u64)
+        .collect();
+
+    producer.join().unwrap();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    latencies
+}
+
+// Com o pipeline cheio, um quadro novo sai a cada FRAME_STAGE_DELAY_MS, mesmo que cada um leve
+// stages vezes isso para atravessar: os estágios trabalham em quadros diferentes ao mesmo tempo.
+
+// Transmitindo os Passos Intermediários de um Fold
+
+// Um fold (ou reduce) combina os valores um a um em um acumulador e normalmente só devolve o
+// resultado final. Aqui o worker envia pelo canal o acumulador depois de cada passo, então quem
+// recebe pode acompanhar o progresso ou mostrar resultados parciais. A função de combinação é
+// passada por quem chama, e o primeiro valor serve de acumulador inicial.
+
+pub fn progressive_aggregate<F: Fn(i64, i64) -> i64 + Send + Sync + 'static>(
+    values: Vec<i64>,
+    combine: F,
+) -> Vec<i64> {
+    let (tx, rx) = mpsc::channel::<i64>();
+
+    let handle = thread::spawn(move || {
+        let mut values = values.into_iter();
+        let Some(mut acc) = values.next() else {
+            return;
+        };
+        tx.send(acc).unwrap();
+        for value in values {
+            acc = combine(acc, value);
+            tx.send(acc).unwrap();
+        }
+    });
+
+    let snapshots: Vec<i64> = rx.iter().collect();
+    handle.join().unwrap();
+    snapshots
+}
+
+// Com soma, os instantâneos são as somas de prefixo; com max, o maior valor visto até cada ponto.
+
+// Um Aperto de Mão para Encerrar Produtor e Consumidor
+
+// Descartar o transmissor avisa o consumidor de que acabou, mas não avisa o produtor de que o
+// consumidor terminou de processar tudo. Às vezes o produtor precisa saber disso antes de sair,
+// por exemplo para liberar um recurso que o consumidor ainda usava. Aqui usamos dois canais: o
+// produtor envia os dados seguidos de um marcador Done, o consumidor processa até o Done e então
+// responde pelo segundo canal. Só depois de receber essa confirmação o produtor termina.
+
+enum ShutdownMessage {
+    Data(i32),
+    Done,
+}
+
+pub fn ordered_shutdown() -> Vec<i32> {
+    let (data_tx, data_rx) = mpsc::channel::<ShutdownMessage>();
+    let (ack_tx, ack_rx) = mpsc::channel::<()>();
+
+    let producer = thread::spawn(move || {
+        for value in [1, 2, 3, 4, 5] {
+            data_tx.send(ShutdownMessage::Data(value)).unwrap();
+        }
+        data_tx.send(ShutdownMessage::Done).unwrap();
+        ack_rx.recv().unwrap();
+    });
+
+    let consumer = thread::spawn(move || {
+        let mut processed = Vec::new();
+        for message in data_rx {
+            match message {
+                ShutdownMessage::Data(value) => processed.push(value * 10),
+                ShutdownMessage::Done => break,
+            }
+        }
+        ack_tx.send(()).unwrap();
+        processed
+    });
+
+    let processed = consumer.join().unwrap();
+    producer.join().unwrap();
+    processed
+}
+
+// O marcador Done permite encerrar o consumidor mesmo que o transmissor continue vivo, e a
+// confirmação garante a ordem: o consumidor termina primeiro, o produtor por último. Se o
+// consumidor morrer sem confirmar, o recv do produtor falha e os unwrap propagam o pânico, em vez
+// de o aperto de mão quebrado parecer um resultado vazio.
+
+// Contando Valores Distintos em Paralelo
+
+// Para saber quantos valores diferentes existem em um vetor grande, cada worker monta um HashSet
+// com os valores do seu pedaço e envia o conjunto inteiro pelo canal. A thread principal faz a
+// união dos conjuntos parciais; valores repetidos entre pedaços aparecem uma única vez no
+// resultado, então o tamanho final é a contagem de distintos.
+
+pub fn parallel_count_distinct(data: Vec<i32>, workers: usize) -> usize {
+    if data.is_empty() {
+        return 0;
+    }
+    let chunk_size = data.len().div_ceil(workers.max(1));
+    let (tx, rx) = mpsc::channel::<HashSet<i32>>();
+
+    for chunk in data.chunks(chunk_size) {
+        let chunk = chunk.to_vec();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let partial: HashSet<i32> = chunk.into_iter().collect();
+            tx.send(partial).unwrap();
+        });
+    }
+    drop(tx);
+
+    let mut distinct = HashSet::new();
+    for partial in rx {
+        distinct.extend(partial);
+    }
+    distinct.len()
+}
+
+// Enviar o HashSet move apenas a estrutura pelo canal, sem copiar os elementos. Quando há muitas
+// repetições dentro de cada pedaço, os conjuntos parciais ficam bem menores que os próprios pedaços.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallible_pipeline_returns_ok_for_valid_input() {
+        assert_eq!(fallible_pipeline(vec![1, 2, 3]), Ok(vec![2, 4, 6]));
+    }
+
+    #[test]
+    fn fallible_pipeline_returns_first_error() {
+        assert_eq!(
+            fallible_pipeline(vec![1, -2, 3, -4]),
+            Err(String::from("valor negativo: -2"))
+        );
+    }
+
+    #[test]
+    fn at_least_once_resends_unacked_message() {
+        let sends = at_least_once(10);
+        assert!(
+            sends > 10,
+            "esperava pelo menos um reenvio, obteve {sends} envios"
+        );
+    }
+
+    #[test]
+    fn stream_transform_filters_and_maps() {
+        assert_eq!(stream_transform(vec![1, 2, 3, 4, 5, 6]), vec![6, 12, 18]);
+    }
+
+    #[test]
+    fn late_subscriber_receives_full_history() {
+        assert_eq!(broadcast_replay(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn channel_latency_reports_one_entry_per_message() {
+        let latencies = channel_latency(100);
+        assert_eq!(latencies.len(), 100);
+        assert!(latencies.iter().all(|latency| *latency >= Duration::ZERO));
+    }
+
+    #[test]
+    fn streaming_primes_up_to_twenty() {
+        assert_eq!(streaming_primes(20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+    }
+
+    #[test]
+    fn transfer_ownership_returns_length() {
+        assert_eq!(transfer_ownership(), 5);
+    }
+
+    #[test]
+    fn bounded_time_collect_drops_stragglers() {
+        let mut collected = bounded_time_collect(vec![10, 800, 20, 1_000], 300);
+        collected.sort();
+        assert_eq!(collected, vec![10, 20]);
+    }
+
+    #[test]
+    fn ping_pong_completes_every_round() {
+        // Os contadores de pings e pongs são conferidos dentro da própria função.
+        ping_pong(1_000);
+        ping_pong(1);
+    }
+
+    #[test]
+    fn ping_pong_measures_round_trips() {
+        assert!(ping_pong(1_000) > Duration::ZERO);
+        assert!(ping_pong(1_000) > ping_pong(0));
+    }
+
+    #[test]
+    fn indexed_results_are_keyed_by_index() {
+        let results = indexed_results(4);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[&3], 9);
+    }
+
+    #[test]
+    fn shared_consumer_processes_every_job() {
+        let counts = shared_consumer(4, 100);
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts.iter().sum::<usize>(), 100);
+    }
+
+    #[test]
+    fn coalesce_groups_a_burst_into_one_batch() {
+        assert_eq!(coalesce(50, 5), vec![vec![0, 1, 2, 3, 4]]);
+    }
+
+    #[test]
+    fn explicit_drop_lets_the_receiver_loop_finish() {
+        let (done_tx, done_rx) = mpsc::channel();
+        thread::spawn(move || done_tx.send(explicit_drop_to_close()).unwrap());
+
+        let received = done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(received, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn merge_sorted_streams_produces_sorted_union() {
+        let streams = vec![vec![1, 4, 9], vec![2, 3, 10, 11], vec![], vec![0, 5]];
+        let mut expected: Vec<i32> = streams.iter().flatten().copied().collect();
+        expected.sort();
+
+        let merged = merge_sorted_streams(streams);
+        assert!(merged.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn timer_fires_when_not_cancelled() {
+        assert!(timer_demo(true));
+    }
+
+    #[test]
+    fn timer_reports_cancellation() {
+        assert!(!timer_demo(false));
+    }
+
+    #[test]
+    fn adaptive_send_gives_up_on_some_messages() {
+        let sent = adaptive_send(1, 20, 1);
+        assert!(sent >= 1);
+        assert!(sent <= 20);
+    }
+
+    #[test]
+    fn rolling_window_averages_last_values() {
+        assert_eq!(
+            rolling_window(vec![1, 2, 3, 4, 5], 3),
+            vec![1.0, 1.5, 2.0, 3.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn map_filter_reduce_matches_sequential() {
+        let data: Vec<i32> = (-20..20).collect();
+        let expected: i64 = data
+            .iter()
+            .map(|&x| x as i64 * x as i64)
+            .filter(|&square| square >= SQUARE_THRESHOLD)
+            .sum();
+
+        assert_eq!(map_filter_reduce(data, 4), expected);
+    }
+
+    #[test]
+    fn query_server_replies_through_each_channel() {
+        let responses = query_server(5);
+        assert_eq!(responses.len(), 5);
+        for (id, response) in responses.into_iter().enumerate() {
+            assert_eq!(response, id as u32 * 2);
+        }
+    }
+
+    #[test]
+    fn race_to_finish_returns_fastest_task() {
+        assert_eq!(race_to_finish(vec![400, 30, 300]), 30);
+    }
+
+    #[test]
+    fn worker_throughput_spreads_jobs_across_workers() {
+        let counts = worker_throughput(4, 1_000);
+        assert_eq!(counts.iter().sum::<usize>(), 1_000);
+        assert!(counts.iter().all(|&count| count >= 1), "{counts:?}");
+    }
+
+    #[test]
+    fn zero_copy_broadcast_returns_payload_length() {
+        assert_eq!(zero_copy_broadcast(vec![7u8; 1024], 4), vec![1024; 4]);
+        assert!(zero_copy_broadcast(vec![1, 2, 3], 0).is_empty());
+    }
+
+    #[test]
+    fn credit_flow_delivers_everything() {
+        assert_eq!(credit_flow(200, 4), 200);
+        assert_eq!(credit_flow(10, 0), 10);
+        assert_eq!(credit_flow(0, 3), 0);
+    }
+
+    #[test]
+    fn run_batch_reports_successes_and_errors() {
+        let report = run_batch(vec![1, -2, 3, -4, 5]);
+        assert_eq!(
+            report,
+            Report {
+                ok: 3,
+                errors: vec![
+                    String::from("entrada 1 inválida: -2"),
+                    String::from("entrada 3 inválida: -4"),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn first_n_results_keeps_the_fastest_tasks() {
+        let mut results = first_n_results(vec![10, 400, 40, 500, 70], 3);
+        results.sort();
+        assert_eq!(results, vec![10, 40, 70]);
+    }
+
+    #[test]
+    fn bounded_pipeline_transforms_in_order() {
+        let input: Vec<i32> = (0..50).collect();
+        let expected: Vec<i32> = input.iter().map(|x| x * 2 + 1).collect();
+
+        assert_eq!(bounded_pipeline(input, 2), expected);
+        assert_eq!(bounded_pipeline(vec![1, 2, 3], 0), vec![3, 5, 7]);
+    }
+
+    #[test]
+    fn bounded_pipeline_applies_backpressure() {
+        // O limite de 2 * buffer + 3 é conferido dentro da própria função.
+        assert_eq!(bounded_pipeline((0..200).collect(), 1).len(), 200);
+    }
+
+    #[test]
+    fn state_machine_reaches_done() {
+        assert_eq!(
+            state_machine(vec!["start", "tick", "tick", "stop"]),
+            State::Done
+        );
+        assert_eq!(
+            state_machine(vec!["tick", "start", "tick"]),
+            State::Running(1)
+        );
+        assert_eq!(state_machine(vec![]), State::Idle);
+    }
+
+    #[test]
+    fn sentinel_drain_stops_at_sentinel() {
+        assert_eq!(sentinel_drain(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn timing_percentiles_are_ordered() {
+        let tasks: Vec<u64> = (1..=20).collect();
+        let (p50, p90, p99) = timing_percentiles(tasks);
+
+        assert!(p50 <= p90);
+        assert!(p90 <= p99);
+        assert!(p50 >= Duration::from_millis(10));
+        assert!(p99 >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn cancelling_a_subscription_stops_the_producer() {
+        let (subscription, rx) = subscribe();
+        let first: Vec<i32> = rx.iter().take(3).collect();
+        assert_eq!(first, vec![0, 1, 2]);
+
+        subscription.cancel();
+
+        let remaining: Vec<i32> = rx.iter().collect();
+        assert!(remaining.iter().all(|&value| value > 2));
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn detect_duplicates_finds_nothing_for_clean_producer() {
+        assert_eq!(detect_duplicates(false), 0);
+    }
+
+    #[test]
+    fn detect_duplicates_counts_resent_ids() {
+        assert_eq!(detect_duplicates(true), 4);
+    }
+
+    #[test]
+    fn fan_out_shutdown_acknowledges_every_job() {
+        let (done_tx, done_rx) = mpsc::channel();
+        thread::spawn(move || done_tx.send(fan_out_shutdown(4, 103)).unwrap());
+
+        assert_eq!(done_rx.recv_timeout(Duration::from_secs(5)), Ok(103));
+    }
+
+    #[test]
+    fn classify_channel_error_send_when_receiver_dropped() {
+        assert_eq!(classify_channel_error(true), ChannelError::Send);
+    }
+
+    #[test]
+    fn classify_channel_error_recv_when_sender_dropped() {
+        assert_eq!(classify_channel_error(false), ChannelError::Recv);
+    }
+
+    #[test]
+    fn two_pass_normalize_matches_sequential_baseline() {
+        let data: Vec<f64> = (0..97).map(|x| ((x * 37) % 101) as f64 - 20.0).collect();
+        let min = data.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = data.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let expected: Vec<f64> = data.iter().map(|x| (x - min) / (max - min)).collect();
+
+        let normalized = two_pass_normalize(data, 4);
+        assert_eq!(normalized, expected);
+        assert!(normalized.iter().all(|x| (0.0..=1.0).contains(x)));
+        assert_eq!(two_pass_normalize(vec![3.0, 3.0], 2), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn group_by_parity_matches_sequential_partition() {
+        let data: Vec<i32> = (-20..37).map(|x| (x * 13) % 29).collect();
+        let (mut evens, mut odds): (Vec<i32>, Vec<i32>) = data.iter().partition(|&&x| x % 2 == 0);
+        evens.sort();
+        odds.sort();
+
+        assert_eq!(group_by_parity(data, 4), (evens, odds));
+        assert_eq!(group_by_parity(vec![], 3), (vec![], vec![]));
+    }
+
+    #[test]
+    fn interruptible_recv_returns_none_after_cancel() {
+        let start = Instant::now();
+        assert_eq!(interruptible_recv(30), None);
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn frame_pipeline_times_every_frame() {
+        let latencies = frame_pipeline(10, 3);
+        assert_eq!(latencies.len(), 10);
+        assert!(latencies
+            .iter()
+            .all(|&us| us >= 3 * FRAME_STAGE_DELAY_MS * 1_000));
+        assert_eq!(frame_pipeline(4, 0).len(), 4);
+    }
+
+    #[test]
+    fn progressive_aggregate_emits_prefix_sums() {
+        let values = vec![3, -1, 4, 1, -5, 9];
+        let expected: Vec<i64> = values
+            .iter()
+            .scan(0, |acc, &x| {
+                *acc += x;
+                Some(*acc)
+            })
+            .collect();
+
+        assert_eq!(progressive_aggregate(values, |a, b| a + b), expected);
+        assert_eq!(
+            progressive_aggregate(vec![2, 7, 1], i64::max),
+            vec![2, 7, 7]
+        );
+        assert!(progressive_aggregate(vec![], |a, b| a + b).is_empty());
+    }
+
+    #[test]
+    fn ordered_shutdown_completes_handshake() {
+        assert_eq!(ordered_shutdown(), vec![10, 20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn parallel_count_distinct_matches_sequential() {
+        let data: Vec<i32> = (0..5_000).map(|x| (x * 7919) % 613 - 300).collect();
+        let expected = data.iter().collect::<HashSet<_>>().len();
+
+        assert_eq!(parallel_count_distinct(data.clone(), 4), expected);
+        assert_eq!(parallel_count_distinct(data, 1), expected);
+        assert_eq!(parallel_count_distinct(vec![], 4), 0);
+    }
+}
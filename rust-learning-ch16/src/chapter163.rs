@@ -13,19 +13,24 @@
 
 use std::sync::{Arc, Mutex};
 
+pub fn with_lock<T, R, F: FnOnce(&mut T) -> R>(m: &Mutex<T>, f: F) -> R {
+    let mut guard = m.lock().unwrap();
+    f(&mut guard)
+}
+
 pub fn call_mutex() {
     let m = Mutex::new(5);
 
-    {
-        let mut num = m.lock().unwrap();
-        *num = 6;
-    }
+    with_lock(&m, |num| *num = 6);
 
     println!("m = {:?}", m);
 }
 
-// Neste exemplo, usamos m.lock() para adquirir o bloqueio e atualizar o valor interno do Mutex. Ao
-// final do escopo, o bloqueio é liberado automaticamente.
+// Neste exemplo, a função with_lock usa m.lock() para adquirir o bloqueio, executa a closure sobre o
+// valor interno do Mutex e devolve o resultado. O guard retornado por lock() é descartado assim que
+// with_lock termina, então o bloqueio é liberado imediatamente. Concentrar o acesso dentro de uma
+// closure deixa explícito o trecho crítico e evita segurar o bloqueio por mais tempo que o
+// necessário.
 
 
 // Compartilhando um Mutex Entre Múltiplas Threads:
@@ -117,11 +122,2142 @@ pub fn atom_ref() {
 // Como os tipos compostos por tipos Send e Sync são automaticamente marcados como tais, geralmente
 // não é necessário implementar esses traits manualmente. Implementar manualmente esses traits
 // envolve código unsafe, e deve-se ter cuidado para manter as garantias de segurança.
-//
+
+// Verificando Send e Sync em Tempo de Compilação
+
+// Como Send e Sync são verificados pelo compilador, podemos escrever funções vazias cujo único
+// papel é exigir o trait. Se o tipo passado não implementar Send (ou Sync), o código simplesmente
+// não compila. Autores de bibliotecas usam esse truque para garantir que seus tipos continuem
+// seguros para threads depois de uma refatoração.
+
+/// Compila apenas se `T` puder ser transferido entre threads.
+///
+/// `Arc<T>` é `Send`:
+///
+/// ```
+/// use rust_learning_ch16::chapter163::assert_send;
+/// use std::sync::Arc;
+///
+/// assert_send::<Arc<i32>>();
+/// ```
+///
+/// `Rc<T>` não é `Send`, então o exemplo abaixo é rejeitado pelo compilador:
+///
+/// ```compile_fail
+/// use rust_learning_ch16::chapter163::assert_send;
+/// use std::rc::Rc;
+///
+/// assert_send::<Rc<i32>>();
+/// ```
+pub fn assert_send<T: Send>() {}
+
+/// Compila apenas se `&T` puder ser compartilhado entre threads.
+pub fn assert_sync<T: Sync>() {}
+
+// Corridas de Dados São Rejeitadas pelo Compilador
+
+// O exemplo da documentação abaixo tenta fazer duas threads modificarem o mesmo Vec sem nenhuma
+// sincronização. O verificador de empréstimo recusa o código: a closure precisa de move para
+// sobreviver à thread, e um mesmo valor não pode ser movido para duas closures. Como o exemplo está
+// marcado com compile_fail, cargo test confirma que ele continua sendo rejeitado. A função em si
+// mostra a versão correta, em que o vetor fica em um Arc<Mutex<Vec<i32>>>.
+
+/// Com o `Vec` dentro de um `Arc<Mutex<...>>`, as duas threads escrevem com segurança:
+///
+/// ```
+/// use rust_learning_ch16::chapter163::race_free_push;
+///
+/// assert_eq!(race_free_push(), vec![1, 2]);
+/// ```
+///
+/// Duas threads escrevendo no mesmo `Vec` sem sincronização não compilam:
+///
+/// ```compile_fail
+/// use std::thread;
+///
+/// let mut v = vec![];
+///
+/// let a = thread::spawn(|| v.push(1));
+/// let b = thread::spawn(|| v.push(2));
+///
+/// a.join().unwrap();
+/// b.join().unwrap();
+/// ```
+pub fn race_free_push() -> Vec<i32> {
+    let v = Arc::new(Mutex::new(vec![]));
+
+    let handles: Vec<_> = [1, 2]
+        .into_iter()
+        .map(|value| {
+            let v = Arc::clone(&v);
+            thread::spawn(move || v.lock().unwrap().push(value))
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let mut result = v.lock().unwrap().clone();
+    result.sort();
+    result
+}
+
 // Resumo
 
 // Rust utiliza crates para várias soluções de concorrência, mais rápidas na evolução do que a
 // biblioteca padrão. Com canais para passagem de mensagens e ponteiros inteligentes como Mutex<T> e
 // Arc<T>, Rust evita condições de corrida e referências inválidas. O sistema de tipos e o
 // verificador de empréstimo asseguram que o código é seguro e rodará corretamente em múltiplas
-// threads após a compilação, tornando a programação concorrente mais acessível e segura.
\ No newline at end of file
+// threads após a compilação, tornando a programação concorrente mais acessível e segura.
+
+//--------------------------------------------------------------------------------------------------
+
+// Compartilhando Dados Imutáveis com Arc
+
+// Nem todo dado compartilhado precisa de um Mutex. Quando as threads apenas leem um valor, basta
+// envolvê-lo em um Arc: cada thread recebe um clone do ponteiro e todas enxergam a mesma estrutura,
+// sem cópias e sem bloqueios. No exemplo abaixo, o conjunto b fica em um Arc<HashSet<i32>> somente
+// leitura, cada worker verifica um pedaço de a e envia pelo canal os valores que também estão em b.
+
+use std::collections::HashSet;
+use std::sync::mpsc;
+
+pub fn parallel_intersection(a: Vec<i32>, b: Vec<i32>, workers: usize) -> Vec<i32> {
+    let set = Arc::new(b.into_iter().collect::<HashSet<i32>>());
+    let (tx, rx) = mpsc::channel();
+    let chunk_size = a.len().div_ceil(workers.max(1)).max(1);
+
+    for chunk in a.chunks(chunk_size) {
+        let chunk = chunk.to_vec();
+        let set = Arc::clone(&set);
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for x in chunk {
+                if set.contains(&x) {
+                    tx.send(x).unwrap();
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    let mut result: Vec<i32> = rx.iter().collect();
+    result.sort();
+    result
+}
+
+// Como a ordem de chegada pelo canal depende do agendamento das threads, ordenamos o resultado no
+// final para que ele seja determinístico.
+
+// Falso Compartilhamento (False Sharing) e Preenchimento
+
+// Processadores carregam a memória em linhas de cache, geralmente de 64 bytes. Quando contadores
+// independentes de threads diferentes ficam na mesma linha, cada escrita invalida a linha nos
+// outros núcleos, mesmo que as threads nunca toquem no mesmo contador. Esse efeito é chamado de
+// falso compartilhamento. A solução é alinhar cada contador na sua própria linha de cache com
+// #[repr(align(64))]. A função abaixo mede as duas organizações e devolve (compactado, alinhado).
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+#[repr(align(64))]
+struct PaddedCounter(AtomicUsize);
+
+fn timed_increments<C: Sync>(
+    counters: &[C],
+    iterations: usize,
+    counter: impl Fn(&C) -> &AtomicUsize + Sync,
+) -> Duration {
+    let start = Instant::now();
+    thread::scope(|s| {
+        for slot in counters {
+            let counter = &counter;
+            s.spawn(move || {
+                for _ in 0..iterations {
+                    counter(slot).fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+pub fn false_sharing_demo(threads: usize, iterations: usize) -> (Duration, Duration) {
+    let packed: Vec<AtomicUsize> = (0..threads).map(|_| AtomicUsize::new(0)).collect();
+    let padded: Vec<PaddedCounter> = (0..threads)
+        .map(|_| PaddedCounter(AtomicUsize::new(0)))
+        .collect();
+    let packed_elapsed = timed_increments(&packed, iterations, |c| c);
+    let padded_elapsed = timed_increments(&padded, iterations, |c| &c.0);
+
+    assert!(packed
+        .iter()
+        .all(|c| c.load(Ordering::Relaxed) == iterations));
+    assert!(padded
+        .iter()
+        .all(|c| c.0.load(Ordering::Relaxed) == iterations));
+    (packed_elapsed, padded_elapsed)
+}
+
+// Logicamente as duas versões fazem exatamente o mesmo trabalho, e os assert! no fim conferem que
+// cada contador, nos dois layouts, chegou a iterations; a diferença está só no layout da memória.
+// Em máquinas com vários núcleos, a versão alinhada costuma ser bem mais rápida.
+
+// Transferência Entre Contas Sem Deadlock
+
+// Quando uma operação precisa de dois Mutex ao mesmo tempo, duas threads podem travar cada uma um
+// deles e ficar esperando pelo outro para sempre. Isso acontece, por exemplo, se uma thread
+// transfere de A para B (travando A e depois B) enquanto outra transfere de B para A (travando B e
+// depois A). A solução clássica é sempre adquirir os bloqueios na mesma ordem global; aqui usamos o
+// endereço de memória de cada conta para decidir quem é travado primeiro.
+
+fn move_funds(src: &Arc<Mutex<i64>>, dst: &Arc<Mutex<i64>>, amount: i64) {
+    let src_first = Arc::as_ptr(src) < Arc::as_ptr(dst);
+    let (first, second) = if src_first { (src, dst) } else { (dst, src) };
+
+    let mut first = first.lock().unwrap();
+    let mut second = second.lock().unwrap();
+    let (from, to) = if src_first {
+        (&mut *first, &mut *second)
+    } else {
+        (&mut *second, &mut *first)
+    };
+
+    *from -= amount;
+    *to += amount;
+}
+
+pub fn transfer(from_initial: i64, to_initial: i64, amount: i64) -> (i64, i64) {
+    let from = Arc::new(Mutex::new(from_initial));
+    let to = Arc::new(Mutex::new(to_initial));
+    let mut handles = vec![];
+
+    for i in 0..4 {
+        let from = Arc::clone(&from);
+        let to = Arc::clone(&to);
+        handles.push(thread::spawn(move || {
+            for _ in 0..100 {
+                if i % 2 == 0 {
+                    move_funds(&from, &to, amount);
+                } else {
+                    move_funds(&to, &from, amount);
+                }
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    move_funds(&from, &to, amount);
+
+    let balances = (*from.lock().unwrap(), *to.lock().unwrap());
+    assert_eq!(balances.0 + balances.1, from_initial + to_initial);
+    balances
+}
+
+// As threads pares e ímpares transferem em sentidos opostos e se anulam; a transferência final
+// feita pela thread principal é a única que altera os saldos. Mesmo com transferências cruzadas
+// acontecendo ao mesmo tempo, o total das duas contas é sempre conservado.
+
+// Teste de Estresse da Ordem de Bloqueio
+
+// Deadlocks costumam aparecer só de vez em quando, dependendo de como as threads são agendadas.
+// Uma forma de ganhar confiança na disciplina de bloqueio é repetir a situação perigosa muitas
+// vezes. Em cada iteração abaixo, duas threads adquirem os mesmos dois Mutex na mesma ordem (a
+// depois b). Todo o trabalho roda em uma thread separada e a thread principal age como um cão de
+// guarda (watchdog): se o trabalho não terminar dentro do tempo limite, consideramos que houve um
+// deadlock e retornamos false.
+
+pub fn deadlock_stress(iterations: usize) -> bool {
+    let (done_tx, done_rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for _ in 0..iterations {
+            let a = Arc::new(Mutex::new(0));
+            let b = Arc::new(Mutex::new(0));
+
+            let handles: Vec<_> = (0..2)
+                .map(|_| {
+                    let a = Arc::clone(&a);
+                    let b = Arc::clone(&b);
+                    thread::spawn(move || {
+                        let mut a = a.lock().unwrap();
+                        let mut b = b.lock().unwrap();
+                        *a += 1;
+                        *b += 1;
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        }
+        done_tx.send(()).unwrap();
+    });
+
+    done_rx.recv_timeout(Duration::from_secs(10)).is_ok()
+}
+
+// Se a segunda thread adquirisse os bloqueios na ordem inversa, cada uma poderia ficar com um dos
+// Mutex e esperar eternamente pelo outro. Com essa versão, deadlock_stress eventualmente retornaria
+// false:
+//
+// thread::spawn(move || {
+//     let mut b = b.lock().unwrap();
+//     let mut a = a.lock().unwrap();
+//     *a += 1;
+//     *b += 1;
+// })
+
+// Cache Preguiçoso com Memoização
+
+// Memoização significa guardar o resultado de um cálculo caro para não repeti-lo. Quando várias
+// threads compartilham o mesmo cache, o HashMap precisa ficar atrás de um Mutex. O Memoizer abaixo
+// guarda o mapa em um Arc<Mutex<HashMap<K, V>>>, então clonar o Memoizer apenas clona o Arc e todos
+// os clones enxergam o mesmo cache.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone)]
+pub struct Memoizer<K, V> {
+    cache: Arc<Mutex<HashMap<K, V>>>,
+}
+
+impl<K: Eq + Hash, V: Clone> Memoizer<K, V> {
+    pub fn new() -> Memoizer<K, V> {
+        Memoizer {
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn get_or_compute(&self, key: K, compute: impl FnOnce() -> V) -> V {
+        let mut cache = self.cache.lock().unwrap();
+        cache.entry(key).or_insert_with(compute).clone()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memoizer<K, V> {
+    fn default() -> Memoizer<K, V> {
+        Memoizer::new()
+    }
+}
+
+pub fn memoize_demo(threads: usize) -> usize {
+    let memo = Memoizer::new();
+    let computations = Arc::new(AtomicUsize::new(0));
+    let mut handles = vec![];
+
+    for i in 0..threads {
+        let memo = memo.clone();
+        let computations = Arc::clone(&computations);
+        handles.push(thread::spawn(move || {
+            let key = i % 3;
+            memo.get_or_compute(key, || {
+                computations.fetch_add(1, Ordering::SeqCst);
+                key * key
+            })
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    computations.load(Ordering::SeqCst)
+}
+
+// O cálculo roda enquanto o bloqueio está adquirido. Isso serializa os cálculos, mas garante que
+// cada chave seja calculada uma única vez, mesmo que várias threads peçam a mesma chave ao mesmo
+// tempo.
+
+// Barra de Progresso Compartilhada
+
+// Um contador atômico é uma forma barata de acompanhar o progresso de várias threads ao mesmo
+// tempo. Cada worker incrementa o mesmo Arc<AtomicUsize> a cada unidade de trabalho concluída, e
+// uma thread monitora lê o valor em intervalos regulares, como faria uma barra de progresso,
+// guardando cada leitura até que o total seja atingido.
+
+pub fn progress_tracker(total: usize, workers: usize) -> Vec<usize> {
+    let workers = workers.max(1);
+    let progress = Arc::new(AtomicUsize::new(0));
+    let mut handles = vec![];
+
+    for i in 0..workers {
+        let share = total / workers + usize::from(i < total % workers);
+        let progress = Arc::clone(&progress);
+        handles.push(thread::spawn(move || {
+            for _ in 0..share {
+                thread::sleep(Duration::from_micros(100));
+                progress.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+    }
+
+    let monitor = {
+        let progress = Arc::clone(&progress);
+        thread::spawn(move || {
+            let mut snapshots = vec![];
+            loop {
+                let current = progress.load(Ordering::SeqCst);
+                snapshots.push(current);
+                if current == total {
+                    return snapshots;
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+        })
+    };
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    monitor.join().unwrap()
+}
+
+// Como o contador só cresce, as leituras do monitor nunca diminuem, e a última sempre é igual ao
+// total de unidades.
+
+// Dividindo o Trabalho em Shards por Hash da Chave
+
+// Um único HashMap atrás de um Mutex vira um gargalo quando muitas threads tentam atualizá-lo. Uma
+// técnica comum para reduzir essa disputa é o sharding: cada item é encaminhado para um shard de
+// acordo com hash(item) % shards. Como a mesma chave sempre cai no mesmo shard, cada thread pode
+// contar seus itens em um HashMap local, sem nenhum bloqueio, e os mapas são unidos no final.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+fn shard_of(item: &str, shards: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    item.hash(&mut hasher);
+    (hasher.finish() % shards as u64) as usize
+}
+
+pub fn sharded_count(items: Vec<String>, shards: usize) -> HashMap<String, usize> {
+    let shards = shards.max(1);
+    let mut buckets: Vec<Vec<String>> = vec![Vec::new(); shards];
+    for item in items {
+        let shard = shard_of(&item, shards);
+        buckets[shard].push(item);
+    }
+
+    let handles: Vec<_> = buckets
+        .into_iter()
+        .map(|bucket| {
+            thread::spawn(move || {
+                let mut counts = HashMap::new();
+                for item in bucket {
+                    *counts.entry(item).or_insert(0) += 1;
+                }
+                counts
+            })
+        })
+        .collect();
+
+    let mut merged = HashMap::new();
+    for handle in handles {
+        merged.extend(handle.join().unwrap());
+    }
+    merged
+}
+
+// Como os shards não compartilham chaves, a junção final é só um extend: nenhuma contagem precisa
+// ser somada com outra.
+
+// Fila Bloqueante Limitada com Condvar
+
+// Um Condvar (variável de condição) permite que uma thread durma até que outra a avise de que algo
+// mudou, sem ficar consultando o Mutex em um laço. A BlockingQueue abaixo guarda os itens em um
+// Mutex<VecDeque<T>> e usa dois Condvar: not_full acorda quem está esperando para colocar itens, e
+// not_empty acorda quem está esperando para retirá-los. put bloqueia quando a fila está cheia e take
+// bloqueia quando ela está vazia.
+
+use std::collections::VecDeque;
+use std::sync::Condvar;
+
+pub struct BlockingQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    capacity: usize,
+    not_full: Condvar,
+    not_empty: Condvar,
+}
+
+impl<T> BlockingQueue<T> {
+    pub fn new(capacity: usize) -> BlockingQueue<T> {
+        BlockingQueue {
+            items: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+        }
+    }
+
+    pub fn put(&self, item: T) {
+        let items = self.items.lock().unwrap();
+        let mut items = self
+            .not_full
+            .wait_while(items, |items| items.len() >= self.capacity)
+            .unwrap();
+        items.push_back(item);
+        self.not_empty.notify_one();
+    }
+
+    pub fn take(&self) -> T {
+        let items = self.items.lock().unwrap();
+        let mut items = self
+            .not_empty
+            .wait_while(items, |items| items.is_empty())
+            .unwrap();
+        let item = items.pop_front().unwrap();
+        self.not_full.notify_one();
+        item
+    }
+}
+
+pub fn blocking_queue_demo(cap: usize, n: usize) -> Vec<i32> {
+    let queue = Arc::new(BlockingQueue::new(cap));
+
+    let producer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            for i in 0..n as i32 {
+                queue.put(i);
+            }
+        })
+    };
+
+    let received = (0..n).map(|_| queue.take()).collect();
+    producer.join().unwrap();
+    received
+}
+
+// wait_while libera o bloqueio enquanto a thread dorme e o readquire ao acordar, verificando a
+// condição de novo. Isso protege contra despertares espúrios, em que o Condvar acorda sem que
+// ninguém tenha chamado notify.
+
+// Medindo o Tempo de Espera pelo Bloqueio
+
+// Disputa por um Mutex (contenção) é invisível até que a medimos. Aqui cada thread marca um
+// Instant antes de chamar lock(), calcula quanto tempo esperou até conseguir o bloqueio e envia
+// essa duração pelo canal. Enquanto segura o bloqueio, a thread dorme um pouco para simular
+// trabalho, o que faz as outras esperarem.
+
+pub fn lock_wait_profile(threads: usize) -> Vec<Duration> {
+    let shared = Arc::new(Mutex::new(0));
+    let (tx, rx) = mpsc::channel();
+
+    for _ in 0..threads {
+        let shared = Arc::clone(&shared);
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let attempt = Instant::now();
+            let mut value = shared.lock().unwrap();
+            let waited = attempt.elapsed();
+            *value += 1;
+            thread::sleep(Duration::from_millis(1));
+            drop(value);
+            tx.send(waited).unwrap();
+        });
+    }
+    drop(tx);
+
+    rx.iter().collect()
+}
+
+// Com mais threads, algumas esperas ficam bem maiores que outras: quem chega por último espera
+// todas as anteriores liberarem o bloqueio.
+
+// Cache de Leitura Frequente com RwLock
+
+// Um Mutex só deixa uma thread por vez acessar o dado, mesmo que todas estejam apenas lendo. O
+// RwLock separa os dois casos: qualquer número de leitores pode segurar read() ao mesmo tempo, e
+// write() dá acesso exclusivo a um único escritor. Em cenários de leitura frequente e escrita rara,
+// como um cache, isso reduz bastante a espera. Abaixo, dois escritores inserem writes entradas
+// enquanto vários leitores consultam as chaves. Se uma chave ainda não chegou ao cache, o leitor
+// solta o bloqueio de leitura, cede a vez e consulta de novo; cada leitura que encontra o valor é
+// contada.
+
+use std::sync::RwLock;
+
+pub fn rwlock_cache(readers: usize, writes: usize) -> usize {
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+    let hits = Arc::new(AtomicUsize::new(0));
+    let mut handles = vec![];
+
+    for writer in 0..2 {
+        let cache = Arc::clone(&cache);
+        handles.push(thread::spawn(move || {
+            for key in (writer..writes).step_by(2) {
+                let key = key as u32;
+                cache.write().unwrap().insert(key, key * 10);
+            }
+        }));
+    }
+
+    for _ in 0..readers {
+        let cache = Arc::clone(&cache);
+        let hits = Arc::clone(&hits);
+        handles.push(thread::spawn(move || {
+            for key in 0..writes as u32 {
+                while !cache.read().unwrap().contains_key(&key) {
+                    thread::yield_now();
+                }
+                hits.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    hits.load(Ordering::Relaxed)
+}
+
+// Como cada leitor insiste até encontrar cada chave, o total é sempre readers * writes, e ele só é
+// atingido se todas as escritas chegaram ao mapa. O que varia entre execuções é quantas consultas
+// cada leitor precisou repetir enquanto os escritores ainda trabalhavam.
+
+// Mutando Campos de uma Struct Protegida por Mutex
+
+// Na prática, o dado protegido por um Mutex raramente é um único inteiro. Aqui o Mutex guarda uma
+// struct Counter inteira, e cada thread altera um campo diferente conforme a paridade do seu
+// índice. Enquanto o guard está vivo, podemos acessar os campos normalmente através dele.
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Counter {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+pub fn nested_mutation(threads: usize) -> Counter {
+    let counter = Arc::new(Mutex::new(Counter::default()));
+    let mut handles = vec![];
+
+    for i in 0..threads {
+        let counter = Arc::clone(&counter);
+        handles.push(thread::spawn(move || {
+            let mut counter = counter.lock().unwrap();
+            if i % 2 == 0 {
+                counter.hits += 1;
+            } else {
+                counter.misses += 1;
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    Arc::try_unwrap(counter).unwrap().into_inner().unwrap()
+}
+
+// Depois do join, a thread principal é a única dona do Arc. Arc::try_unwrap devolve o Mutex e
+// into_inner devolve o Counter, sem precisar cloná-lo.
+
+// Contador Atômico e Contador com Mutex Dão o Mesmo Resultado
+
+// O atom_ref mostrou um contador com Arc<Mutex<i32>>. Para um simples contador, um AtomicUsize faz
+// o mesmo trabalho sem bloqueio: fetch_add é uma única operação indivisível. A função abaixo roda
+// os dois contadores com os mesmos parâmetros e confere se chegaram ao mesmo total, servindo como
+// uma verificação cruzada de que ambos os primitivos são corretos sob carga.
+
+fn mutex_counter_total(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(Mutex::new(0));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    *counter.lock().unwrap() += 1;
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let total = *counter.lock().unwrap();
+    total
+}
+
+fn atomic_counter_total(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    counter.load(Ordering::Relaxed)
+}
+
+pub fn equivalence_stress(threads: usize, increments: usize) -> bool {
+    mutex_counter_total(threads, increments) == atomic_counter_total(threads, increments)
+}
+
+// Ordering::Relaxed basta para um contador: só nos interessa que nenhum incremento se perca, não a
+// ordem relativa entre eles e outras operações de memória. O join garante que todos os incrementos
+// estejam visíveis antes da leitura final.
+
+// Contando Falhas de try_lock Sob Contenção
+
+// lock() bloqueia a thread até que o Mutex fique livre. Já try_lock() tenta uma única vez e
+// retorna Err(TryLockError::WouldBlock) se outra thread estiver com o bloqueio, permitindo que a
+// thread faça outra coisa em vez de dormir. Abaixo, cada thread precisa fazer attempts incrementos
+// usando apenas try_lock: quando falha, registra a falha em um contador atômico e tenta de novo.
+
+pub fn try_lock_contention(threads: usize, attempts: usize) -> usize {
+    let counter = Arc::new(Mutex::new(0));
+    let failures = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            let failures = Arc::clone(&failures);
+            thread::spawn(move || {
+                let mut done = 0;
+                while done < attempts {
+                    match counter.try_lock() {
+                        Ok(mut value) => {
+                            *value += 1;
+                            done += 1;
+                        }
+                        Err(_) => {
+                            failures.fetch_add(1, Ordering::Relaxed);
+                            std::hint::spin_loop();
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(*counter.lock().unwrap(), threads * attempts);
+    failures.load(Ordering::Relaxed)
+}
+
+// Mesmo com muitas falhas, o total final é exato: cada incremento só acontece quando try_lock
+// consegue o bloqueio, e a thread insiste até completar os seus. O assert_eq! antes do retorno
+// confere esse total.
+
+// Um Tipo de Contador Concorrente
+
+// Em atom_ref, cada thread precisa de um Arc::clone explícito antes do spawn. Podemos esconder esse
+// detalhe em um tipo próprio: ConcurrentCounter guarda um Arc<AtomicUsize>, e clonar o contador
+// apenas clona o Arc, de modo que todos os clones incrementam o mesmo valor.
+
+#[derive(Clone, Default)]
+pub struct ConcurrentCounter {
+    value: Arc<AtomicUsize>,
+}
+
+impl ConcurrentCounter {
+    pub fn new() -> ConcurrentCounter {
+        ConcurrentCounter::default()
+    }
+
+    pub fn increment(&self) {
+        self.value.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn get(&self) -> usize {
+        self.value.load(Ordering::SeqCst)
+    }
+}
+
+pub fn counter_across_threads(threads: usize) -> usize {
+    let counter = ConcurrentCounter::new();
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = counter.clone();
+            thread::spawn(move || counter.increment())
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    counter.get()
+}
+
+// Gerador de Números Pseudoaleatórios Compartilhado
+
+// Um gerador congruencial linear (LCG) guarda um estado e, a cada sorteio, calcula o próximo estado
+// a partir do anterior. Se duas threads lessem e atualizassem esse estado ao mesmo tempo, ambas
+// poderiam calcular o mesmo próximo valor e o sorteio se repetiria. Guardando o estado em um
+// Arc<Mutex<u64>>, a leitura e a atualização acontecem dentro do mesmo bloqueio, e cada sorteio
+// avança o estado exatamente uma vez.
+
+pub fn shared_rng(threads: usize, draws: usize) -> Vec<u64> {
+    let state = Arc::new(Mutex::new(0x2545F4914F6CDD1D_u64));
+    let (tx, rx) = mpsc::channel();
+
+    for _ in 0..threads {
+        let state = Arc::clone(&state);
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for _ in 0..draws {
+                let value = with_lock(&state, |state| {
+                    *state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    *state
+                });
+                tx.send(value).unwrap();
+            }
+        });
+    }
+    drop(tx);
+
+    rx.iter().collect()
+}
+
+// Com esses multiplicador e incremento, o LCG percorre todos os 2^64 estados antes de repetir
+// algum, então nenhum sorteio se repete enquanto o total de sorteios for menor que isso.
+
+// Observando a Ordem em que o Bloqueio Foi Concedido
+
+// O Mutex da biblioteca padrão não promete nenhuma ordem justa: quando várias threads esperam, não
+// há garantia de que a primeira a pedir seja a primeira a conseguir. Para observar isso, cada
+// thread registra o seu id em um log assim que adquire o bloqueio. Como o log fica dentro do
+// próprio Mutex, a ordem do vetor é exatamente a ordem em que o bloqueio foi concedido.
+
+pub fn lock_grant_order(threads: usize) -> Vec<usize> {
+    let log = Arc::new(Mutex::new(Vec::with_capacity(threads)));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|id| {
+            let log = Arc::clone(&log);
+            thread::spawn(move || log.lock().unwrap().push(id))
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let order = log.lock().unwrap().clone();
+    order
+}
+
+// Rodando várias vezes, a ordem muda: ela depende de quando cada thread foi agendada, não da ordem
+// em que foram criadas.
+
+// Largada Sincronizada com Mutex e Condvar
+
+// Ao criar threads em um laço, a primeira já está rodando enquanto as últimas ainda estão sendo
+// criadas. Para que todas comecem juntas, como em uma corrida, usamos um portão de largada: um
+// Arc<(Mutex<bool>, Condvar)>. Cada thread espera no Condvar até o bool virar true; a thread
+// principal espera todas ficarem prontas, liga o bool e chama notify_all, acordando todas de uma
+// vez. Cada thread registra o Instant em que largou.
+
+pub fn synchronized_start(threads: usize) -> Vec<Instant> {
+    let gate = Arc::new((Mutex::new(false), Condvar::new()));
+    let ready = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let gate = Arc::clone(&gate);
+            let ready = Arc::clone(&ready);
+            thread::spawn(move || {
+                let (started, signal) = &*gate;
+                let started = started.lock().unwrap();
+                ready.fetch_add(1, Ordering::SeqCst);
+                let _started = signal.wait_while(started, |started| !*started).unwrap();
+                Instant::now()
+            })
+        })
+        .collect();
+
+    while ready.load(Ordering::SeqCst) < threads {
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let (started, signal) = &*gate;
+    *started.lock().unwrap() = true;
+    signal.notify_all();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+// Cada thread só incrementa ready depois de adquirir o Mutex do portão. Como a thread principal
+// precisa do mesmo Mutex para abrir o portão, ela nunca consegue abri-lo entre o momento em que uma
+// thread se declara pronta e o momento em que começa a esperar no Condvar.
+
+// Contador com Limite Usando fetch_update
+
+// fetch_add sempre soma, mas e se o contador não puder passar de um limite? Ler o valor, comparar
+// e depois gravar em operações separadas deixaria uma janela para outra thread mexer no meio.
+// fetch_update resolve isso com um laço de compare-and-swap: ele calcula o novo valor a partir do
+// atual e só grava se ninguém tiver alterado o contador nesse meio tempo, tentando de novo caso
+// contrário. Se a closure retornar None, nada é gravado e fetch_update retorna Err.
+
+pub fn clamped_counter(threads: usize, max: usize) -> usize {
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..max {
+                    let result = counter.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                        if n < max {
+                            Some(n + 1)
+                        } else {
+                            None
+                        }
+                    });
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    counter.load(Ordering::SeqCst)
+}
+
+// Mesmo com várias threads tentando incrementar ao mesmo tempo, o contador para exatamente em max.
+
+// Justiça na Disputa pelo Bloqueio
+
+// Quando uma thread libera um Mutex e tenta pegá-lo de novo imediatamente, ela costuma levar
+// vantagem sobre as que estavam dormindo esperando, porque já está rodando. Isso pode fazer com que
+// algumas threads fiquem com quase todo o trabalho. Abaixo, as threads consomem unidades de uma
+// pilha de trabalho compartilhada em dois cenários: no primeiro, cada thread cede a vez com
+// thread::yield_now depois de liberar o bloqueio; no segundo, volta a disputar na hora. Como medida
+// de equilíbrio, devolvemos a menor quantidade de unidades que alguma thread conseguiu processar.
+// Uma Barrier faz todas as threads começarem juntas, para que nenhuma encontre a pilha vazia só por
+// ter sido criada por último.
+
+const FAIRNESS_UNITS_PER_THREAD: usize = 200;
+
+fn run_fairness(threads: usize, yield_after_release: bool) -> Vec<usize> {
+    let remaining = Arc::new(Mutex::new(threads * FAIRNESS_UNITS_PER_THREAD));
+    let start = Arc::new(Barrier::new(threads));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let remaining = Arc::clone(&remaining);
+            let start = Arc::clone(&start);
+            thread::spawn(move || {
+                start.wait();
+                let mut handled = 0;
+                loop {
+                    {
+                        let mut remaining = remaining.lock().unwrap();
+                        if *remaining == 0 {
+                            break;
+                        }
+                        *remaining -= 1;
+                    }
+                    handled += 1;
+                    if yield_after_release {
+                        thread::yield_now();
+                    }
+                }
+                handled
+            })
+        })
+        .collect();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+pub fn fairness_comparison(threads: usize) -> (usize, usize) {
+    let yielding = run_fairness(threads, true);
+    let greedy = run_fairness(threads, false);
+    (
+        yielding.into_iter().min().unwrap_or(0),
+        greedy.into_iter().min().unwrap_or(0),
+    )
+}
+
+// Os números variam bastante de máquina para máquina, mas em geral o cenário com yield_now
+// distribui o trabalho de forma mais uniforme, enquanto no cenário guloso algumas threads podem
+// terminar com pouquíssimas unidades.
+
+// Broadcast com Condvar e notify_all
+
+// Um Condvar também pode avisar várias threads de uma vez. Enquanto notify_one acorda apenas uma
+// das threads que esperam, notify_all acorda todas. Abaixo, vários assinantes esperam até que um
+// valor seja publicado em um Mutex<Option<i32>>. Como em synchronized_start, cada assinante se
+// declara pronto com o Mutex travado, e o produtor só publica depois que todos estão prontos; assim
+// todos já estão dormindo no Condvar quando o produtor grava o valor e chama notify_all. Cada
+// assinante que acorda e lê o valor publicado conta como notificado.
+
+pub fn condvar_broadcast(subscribers: usize) -> usize {
+    let shared = Arc::new((Mutex::new(None), Condvar::new()));
+    let ready = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..subscribers)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            let ready = Arc::clone(&ready);
+            thread::spawn(move || {
+                let (value, published) = &*shared;
+                let value = value.lock().unwrap();
+                ready.fetch_add(1, Ordering::SeqCst);
+                let value = published
+                    .wait_while(value, |value| value.is_none())
+                    .unwrap();
+                *value == Some(42)
+            })
+        })
+        .collect();
+
+    while ready.load(Ordering::SeqCst) < subscribers {
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let producer = {
+        let shared = Arc::clone(&shared);
+        thread::spawn(move || {
+            let (value, published) = &*shared;
+            *value.lock().unwrap() = Some(42);
+            published.notify_all();
+        })
+    };
+    producer.join().unwrap();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .filter(|&notified| notified)
+        .count()
+}
+
+// Como todos os assinantes já estão esperando antes da publicação, nenhum deles encontra o valor
+// pronto ao chegar: cada um só sai de wait_while porque notify_all o acordou. Com notify_one,
+// apenas um acordaria e os demais ficariam esperando para sempre.
+
+// Limitador de Taxa com Janela Deslizante
+
+// Um limitador de taxa permite no máximo max_per_window requisições em qualquer intervalo de
+// window_ms. A versão com janela deslizante guarda o instante de cada requisição aceita em um
+// VecDeque: antes de decidir, descarta do início da fila os instantes que já saíram da janela e
+// aceita a nova requisição só se ainda houver espaço. Como várias threads consultam o limitador ao
+// mesmo tempo, a fila fica atrás de um Mutex e toda a decisão acontece dentro do bloqueio.
+
+pub struct SlidingWindowLimiter {
+    accepted: Mutex<VecDeque<Instant>>,
+    max_per_window: usize,
+    window: Duration,
+}
+
+impl SlidingWindowLimiter {
+    pub fn new(max_per_window: usize, window: Duration) -> SlidingWindowLimiter {
+        SlidingWindowLimiter {
+            accepted: Mutex::new(VecDeque::new()),
+            max_per_window,
+            window,
+        }
+    }
+
+    pub fn try_acquire(&self) -> bool {
+        let now = Instant::now();
+        let mut accepted = self.accepted.lock().unwrap();
+        while let Some(&oldest) = accepted.front() {
+            if now.duration_since(oldest) < self.window {
+                break;
+            }
+            accepted.pop_front();
+        }
+
+        if accepted.len() < self.max_per_window {
+            accepted.push_back(now);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub fn sliding_rate_limit(requests: usize, max_per_window: usize, window_ms: u64) -> usize {
+    let limiter = Arc::new(SlidingWindowLimiter::new(
+        max_per_window,
+        Duration::from_millis(window_ms),
+    ));
+    let threads = 4;
+
+    let handles: Vec<_> = (0..threads)
+        .map(|i| {
+            let limiter = Arc::clone(&limiter);
+            let share = requests / threads + usize::from(i < requests % threads);
+            thread::spawn(move || (0..share).filter(|_| limiter.try_acquire()).count())
+        })
+        .collect();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .sum()
+}
+
+// As requisições recusadas não entram na fila, então não "gastam" espaço da janela.
+
+// Construindo uma String Compartilhada
+
+// String também pode ficar dentro de um Mutex. Cada thread abaixo trava o Arc<Mutex<String>> e
+// acrescenta uma linha com o seu fragmento. Como a ordem em que as threads conseguem o bloqueio
+// varia a cada execução, ordenamos as linhas no final para que o resultado seja sempre o mesmo.
+
+pub fn concurrent_string_build(threads: usize) -> String {
+    let shared = Arc::new(Mutex::new(String::new()));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|i| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                let fragment = format!("fragmento da thread {i:03}\n");
+                shared.lock().unwrap().push_str(&fragment);
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let built = shared.lock().unwrap().clone();
+    let mut lines: Vec<&str> = built.lines().collect();
+    lines.sort();
+    lines.join("\n")
+}
+
+// O fragmento é formatado antes de travar o Mutex. Assim, o trecho crítico se resume ao push_str,
+// e o bloqueio é segurado pelo menor tempo possível.
+
+// Compartilhando um Arc Através de Threads Aninhadas
+
+// Um Arc pode ser clonado por qualquer thread que já tenha um, não só pela thread principal. Abaixo,
+// cada nível de uma cadeia de threads incrementa o contador compartilhado, clona o Arc e cria a
+// thread do próximo nível, esperando por ela com join. Não importa quão profunda seja a cadeia:
+// todas as threads apontam para o mesmo Mutex.
+
+fn spawn_level(counter: Arc<Mutex<i64>>, remaining: usize) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        *counter.lock().unwrap() += 1;
+
+        if remaining > 1 {
+            let next = Arc::clone(&counter);
+            spawn_level(next, remaining - 1).join().unwrap();
+        }
+    })
+}
+
+pub fn nested_arc_shared(levels: usize) -> i64 {
+    let counter = Arc::new(Mutex::new(0));
+
+    if levels > 0 {
+        spawn_level(Arc::clone(&counter), levels).join().unwrap();
+    }
+
+    let total = *counter.lock().unwrap();
+    total
+}
+
+// Quando o nível mais profundo termina, cada thread descarta o seu clone ao sair; no final só a
+// referência da thread principal continua viva.
+
+// Balanceador de Carga que Escolhe o Worker Menos Ocupado
+
+// Distribuir jobs em rodízio (round-robin) funciona bem quando todos custam o mesmo. Com custos
+// diferentes, um worker pode acumular os jobs pesados enquanto outro fica ocioso. Uma estratégia
+// melhor é manter a carga pendente de cada worker em um Arc<Mutex<Vec<u64>>> e mandar cada job para
+// quem tem menos carga no momento. O dispatcher soma o custo do job ao escolher o worker, e o
+// worker subtrai esse custo quando termina. O retorno indica qual worker recebeu cada job.
+
+pub fn least_busy_balancer(jobs: Vec<u64>, workers: usize) -> Vec<usize> {
+    let workers = workers.max(1);
+    let loads = Arc::new(Mutex::new(vec![0u64; workers]));
+
+    let mut senders = Vec::with_capacity(workers);
+    let mut handles = Vec::with_capacity(workers);
+    for id in 0..workers {
+        let (tx, rx) = mpsc::channel::<u64>();
+        let loads = Arc::clone(&loads);
+        senders.push(tx);
+        handles.push(thread::spawn(move || {
+            for cost in rx {
+                thread::sleep(Duration::from_millis(cost));
+                loads.lock().unwrap()[id] -= cost;
+            }
+        }));
+    }
+
+    let mut assignments = Vec::with_capacity(jobs.len());
+    for cost in jobs {
+        let chosen = {
+            let mut loads = loads.lock().unwrap();
+            let (chosen, _) = loads
+                .iter()
+                .enumerate()
+                .min_by_key(|&(_, load)| *load)
+                .unwrap();
+            loads[chosen] += cost;
+            chosen
+        };
+        senders[chosen].send(cost).unwrap();
+        assignments.push(chosen);
+    }
+    drop(senders);
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assignments
+}
+
+// A escolha e o incremento da carga acontecem dentro do mesmo bloqueio; caso contrário, um worker
+// poderia terminar um job entre a leitura e a escrita e a carga registrada ficaria errada.
+
+// Registrando uma Ordem Total com um Contador de Sequência
+
+// A ordem em que duas threads intercalam seus eventos muda a cada execução. Para registrar essa
+// ordem de forma reproduzível, cada evento recebe um número de sequência de um AtomicUsize
+// compartilhado: fetch_add devolve o valor anterior e incrementa em uma única operação, então
+// nenhum número se repete e nenhum fica de fora. Cada thread guarda seus próprios eventos, e ao
+// final juntamos tudo e ordenamos pelo número de sequência.
+
+pub fn replay_log(events: usize) -> Vec<(u8, usize)> {
+    let sequence = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..2u8)
+        .map(|id| {
+            let sequence = Arc::clone(&sequence);
+            thread::spawn(move || {
+                (0..events)
+                    .map(|_| (id, sequence.fetch_add(1, Ordering::SeqCst)))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut log: Vec<(u8, usize)> = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    log.sort_by_key(|&(_, seq)| seq);
+    log
+}
+
+// Reexecutar os eventos na ordem do log reproduz exatamente o intercalamento observado, mesmo que
+// a próxima execução das threads intercale de outra forma.
+
+// Pré-Alocando um Vec Compartilhado para Evitar Realocações
+
+// Quando um Vec enche, push aloca um buffer maior e copia os elementos para ele. Com várias threads
+// empurrando valores em um Arc<Mutex<Vec<u64>>>, essa cópia acontece com o bloqueio travado e
+// atrasa todas as outras. Se soubermos quantos elementos virão, Vec::with_capacity reserva o espaço
+// de uma vez. Abaixo, cada push compara a capacidade antes e depois, e o total de realocações
+// observadas é devolvido.
+
+pub fn shared_vec_reuse(threads: usize, pushes: usize) -> usize {
+    let data = Arc::new(Mutex::new(Vec::with_capacity(threads * pushes)));
+    let reallocations = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|id| {
+            let data = Arc::clone(&data);
+            let reallocations = Arc::clone(&reallocations);
+            thread::spawn(move || {
+                for i in 0..pushes {
+                    let mut data = data.lock().unwrap();
+                    let before = data.capacity();
+                    data.push((id * pushes + i) as u64);
+                    if data.capacity() != before {
+                        reallocations.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(data.lock().unwrap().len(), threads * pushes);
+    reallocations.load(Ordering::Relaxed)
+}
+
+// Com a capacidade exata, nenhuma realocação acontece; partindo de um Vec vazio, o crescimento
+// dobra a capacidade a cada vez, então o número de realocações cresce só de forma logarítmica.
+
+// Eleição de Líder: o Primeiro a Escrever Vence
+
+// Em muitos sistemas, um entre vários participantes precisa assumir um papel especial, como
+// coordenar os demais. Uma eleição simples: todas as threads disputam um Arc<Mutex<Option<usize>>>.
+// Dentro do bloqueio, cada uma confere se já existe líder; se não existir, grava o próprio id. Como
+// conferir e gravar acontecem sob o mesmo bloqueio, só a primeira consegue, e todas as outras
+// aceitam o líder que encontram.
+
+pub fn elect_leader(threads: usize) -> usize {
+    let leader = Arc::new(Mutex::new(None));
+
+    let handles: Vec<_> = (0..threads.max(1))
+        .map(|id| {
+            let leader = Arc::clone(&leader);
+            thread::spawn(move || *leader.lock().unwrap().get_or_insert(id))
+        })
+        .collect();
+
+    let views: Vec<usize> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    let elected = leader.lock().unwrap().unwrap();
+    assert!(views.iter().all(|&view| view == elected));
+    elected
+}
+
+// Option::get_or_insert faz a comparação e a escrita de uma vez: devolve o valor existente ou
+// insere o novo. Qual thread vence depende do escalonador, mas sempre haverá exatamente uma, e a
+// função confere que todas as threads viram o mesmo líder.
+
+// Computação em Fases Sincronizadas por uma Barrier
+
+// No modelo "bulk-synchronous", as threads trabalham em rodadas: ninguém começa a fase seguinte
+// antes de todos terminarem a atual. std::sync::Barrier implementa esse ponto de encontro: wait
+// bloqueia até que o número combinado de threads o tenha chamado. Aqui a thread coordenadora também
+// participa da barreira, o que permite a ela marcar o início e o fim de cada fase e medir quanto
+// tempo a fase levou, ou seja, o tempo da thread mais lenta.
+
+use std::sync::Barrier;
+
+pub fn phased_computation(threads: usize, phases: usize) -> Vec<Duration> {
+    let threads = threads.max(1);
+    let barrier = Arc::new(Barrier::new(threads + 1));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|id| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                for phase in 0..phases {
+                    barrier.wait();
+                    thread::sleep(Duration::from_millis(1 + ((id + phase) % 3) as u64));
+                    barrier.wait();
+                }
+            })
+        })
+        .collect();
+
+    let mut durations = Vec::with_capacity(phases);
+    for _ in 0..phases {
+        barrier.wait();
+        let start = Instant::now();
+        barrier.wait();
+        durations.push(start.elapsed());
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    durations
+}
+
+// São duas barreiras por fase: a primeira libera todos ao mesmo tempo para começar, e a segunda
+// garante que todos terminaram. Uma Barrier pode ser reutilizada quantas vezes quisermos.
+
+// Pilha Sem Bloqueio (Treiber) Versus Pilha com Mutex
+
+// Átomos não servem só para contadores. A pilha de Treiber é uma estrutura sem bloqueio: o topo é
+// um AtomicPtr para o primeiro nó, e push cria um nó novo apontando para o topo atual e tenta
+// trocar o topo com compare_exchange. Se outra thread mudou o topo nesse meio-tempo, a troca falha,
+// o nó é religado ao novo topo e a tentativa se repete. Nenhuma thread espera por um bloqueio, mas
+// em troca precisamos de código unsafe para lidar com os ponteiros crus. Abaixo, comparamos o tempo
+// de push concorrente dela com o de uma pilha simples em um Mutex<Vec<T>>.
+
+use std::ptr;
+use std::sync::atomic::AtomicPtr;
+
+struct Node<T> {
+    value: T,
+    next: *mut Node<T>,
+}
+
+pub struct TreiberStack<T> {
+    head: AtomicPtr<Node<T>>,
+}
+
+unsafe impl<T: Send> Send for TreiberStack<T> {}
+unsafe impl<T: Send> Sync for TreiberStack<T> {}
+
+impl<T> TreiberStack<T> {
+    pub fn new() -> Self {
+        TreiberStack {
+            head: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    pub fn push(&self, value: T) {
+        let node = Box::into_raw(Box::new(Node {
+            value,
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.head.load(Ordering::Acquire);
+        loop {
+            // SAFETY: o nó ainda não foi publicado, então só esta thread o acessa.
+            unsafe { (*node).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Acquire)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        let mut count = 0;
+        let mut current = self.head.load(Ordering::Acquire);
+        while !current.is_null() {
+            count += 1;
+            // SAFETY: nós publicados só são liberados no drop, que exige acesso exclusivo.
+            current = unsafe { (*current).next };
+        }
+        count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    pub fn into_vec(mut self) -> Vec<T> {
+        let mut values = Vec::new();
+        let mut current = std::mem::replace(self.head.get_mut(), ptr::null_mut());
+        while !current.is_null() {
+            // SAFETY: self foi consumido, então nenhuma outra thread ainda enxerga os nós.
+            let node = unsafe { Box::from_raw(current) };
+            current = node.next;
+            values.push(node.value);
+        }
+        values
+    }
+}
+
+impl<T> Default for TreiberStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        let mut current = *self.head.get_mut();
+        while !current.is_null() {
+            // SAFETY: cada nó veio de Box::into_raw e é liberado exatamente uma vez.
+            let node = unsafe { Box::from_raw(current) };
+            current = node.next;
+        }
+    }
+}
+
+pub struct LockedStack<T> {
+    items: Mutex<Vec<T>>,
+}
+
+impl<T> LockedStack<T> {
+    pub fn new() -> Self {
+        LockedStack {
+            items: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn push(&self, value: T) {
+        self.items.lock().unwrap().push(value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.lock().unwrap().is_empty()
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items.into_inner().unwrap()
+    }
+}
+
+impl<T> Default for LockedStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn timed_pushes<F: Fn(usize) + Sync>(threads: usize, pushes: usize, push: F) -> Duration {
+    let start = Instant::now();
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                for i in 0..pushes {
+                    push(i);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+pub fn stack_throughput(threads: usize, pushes: usize) -> (Duration, Duration) {
+    let lock_free = TreiberStack::new();
+    let locked = LockedStack::new();
+    (
+        timed_pushes(threads, pushes, |i| lock_free.push(i)),
+        timed_pushes(threads, pushes, |i| locked.push(i)),
+    )
+}
+
+// Esta pilha não tem pop concorrente: into_vec só retira os valores depois de consumir a pilha,
+// quando nenhuma outra thread pode mais acessá-la. Um pop sem bloqueio precisa resolver o problema
+// ABA e decidir quando é seguro liberar um nó que outra thread ainda pode estar lendo, o que
+// normalmente exige técnicas como hazard pointers ou coleta por épocas, oferecidas por crates como
+// crossbeam.
+
+// Um Pool de Objetos Seguro para Threads
+
+// Criar certos objetos é caro (conexões, buffers grandes), então é comum mantê-los em um pool e
+// reaproveitá-los. ObjectPool guarda os objetos livres em um Mutex<Vec<T>>: acquire retira um (ou
+// devolve None se todos estiverem em uso) e release o coloca de volta. Em pool_demo, cada worker
+// pega um objeto, usa, devolve, e repete; quando o pool está vazio, cede a vez e tenta de novo. O
+// pool mantém um contador atômico de objetos emprestados e registra o maior valor que ele atingiu.
+
+const POOL_ROUNDS: usize = 5;
+
+pub struct ObjectPool<T> {
+    items: Mutex<Vec<T>>,
+    in_use: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl<T> ObjectPool<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        ObjectPool {
+            items: Mutex::new(items),
+            in_use: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn acquire(&self) -> Option<T> {
+        let mut items = self.items.lock().unwrap();
+        let item = items.pop()?;
+        let now = self.in_use.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        Some(item)
+    }
+
+    pub fn release(&self, item: T) {
+        let mut items = self.items.lock().unwrap();
+        self.in_use.fetch_sub(1, Ordering::SeqCst);
+        items.push(item);
+    }
+
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
+fn pool_workers<T: Send>(pool: &ObjectPool<T>, workers: usize) -> usize {
+    let acquisitions = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        for _ in 0..workers {
+            s.spawn(|| {
+                let mut rounds = 0;
+                while rounds < POOL_ROUNDS {
+                    let Some(object) = pool.acquire() else {
+                        thread::yield_now();
+                        continue;
+                    };
+                    acquisitions.fetch_add(1, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(1));
+                    pool.release(object);
+                    rounds += 1;
+                }
+            });
+        }
+    });
+
+    acquisitions.into_inner()
+}
+
+pub fn pool_demo(pool_size: usize, workers: usize) -> usize {
+    if pool_size == 0 {
+        return 0;
+    }
+    let pool = ObjectPool::new((0..pool_size).collect::<Vec<usize>>());
+    pool_workers(&pool, workers)
+}
+
+// O contador in_use só muda com o Mutex do pool travado, junto com o Vec de objetos livres; assim a
+// contagem nunca fica fora de sincronia com os objetos emprestados e o pico nunca passa do tamanho
+// do pool.
+
+// Reduzindo a Contenção com Backoff Exponencial
+
+// No exemplo de try_lock acima, uma thread que falha tenta de novo imediatamente, disputando o
+// Mutex sem parar. Com backoff exponencial, cada falha seguida faz a thread dormir o dobro do tempo
+// da anterior, até um limite; um sucesso volta a espera ao valor inicial. Assim, sob muita
+// contenção, as threads se afastam e deixam o dono do bloqueio trabalhar. Cada thread conta quantas
+// vezes dormiu e devolve esse número pelo join.
+
+const BACKOFF_START_MICROS: u64 = 1;
+const BACKOFF_MAX_MICROS: u64 = 256;
+
+pub fn backoff_counter(threads: usize, increments: usize) -> (usize, u64) {
+    let counter = Arc::new(Mutex::new(0));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                let mut sleeps = 0u64;
+                let mut delay = BACKOFF_START_MICROS;
+                let mut done = 0;
+                while done < increments {
+                    match counter.try_lock() {
+                        Ok(mut value) => {
+                            *value += 1;
+                            done += 1;
+                            delay = BACKOFF_START_MICROS;
+                        }
+                        Err(_) => {
+                            sleeps += 1;
+                            thread::sleep(Duration::from_micros(delay));
+                            delay = (delay * 2).min(BACKOFF_MAX_MICROS);
+                        }
+                    }
+                }
+                sleeps
+            })
+        })
+        .collect();
+
+    let sleeps = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    let total = *counter.lock().unwrap();
+    (total, sleeps)
+}
+
+// O limite impede que uma thread azarada durma tempo demais depois de uma sequência longa de
+// falhas. Bibliotecas reais costumam também sortear a espera (jitter) para que as threads não
+// acordem todas juntas.
+
+// Inicialização Preguiçosa com Verificação Dupla
+
+// Quando um valor caro deve ser criado só uma vez, mas lido por muitas threads, travar um Mutex a
+// cada leitura desperdiça tempo depois que ele já existe. No padrão de verificação dupla, uma flag
+// AtomicBool indica se o valor já foi inicializado. O caminho rápido só lê a flag e, se ela for
+// true, lê o valor publicado em um AtomicI32, sem tocar no Mutex. Se a flag ainda for false, a
+// thread trava o Mutex e confere de novo lá dentro, porque outra thread pode ter inicializado o
+// valor enquanto esta esperava pelo bloqueio. LazyValue conta quantas vezes a inicialização rodou
+// e quantas vezes alguma thread precisou entrar no caminho lento.
+
+use std::sync::atomic::{AtomicBool, AtomicI32};
+
+pub struct LazyValue {
+    initialized: AtomicBool,
+    published: AtomicI32,
+    slot: Mutex<Option<i32>>,
+    init_count: AtomicUsize,
+    slow_path_entries: AtomicUsize,
+}
+
+impl LazyValue {
+    pub fn new() -> Self {
+        LazyValue {
+            initialized: AtomicBool::new(false),
+            published: AtomicI32::new(0),
+            slot: Mutex::new(None),
+            init_count: AtomicUsize::new(0),
+            slow_path_entries: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get(&self) -> i32 {
+        if self.initialized.load(Ordering::Acquire) {
+            return self.published.load(Ordering::Relaxed);
+        }
+
+        self.slow_path_entries.fetch_add(1, Ordering::SeqCst);
+        let mut slot = self.slot.lock().unwrap();
+        match *slot {
+            Some(value) => value,
+            None => {
+                self.init_count.fetch_add(1, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(5));
+                let value = 42;
+                *slot = Some(value);
+                self.published.store(value, Ordering::Relaxed);
+                self.initialized.store(true, Ordering::Release);
+                value
+            }
+        }
+    }
+
+    pub fn init_count(&self) -> usize {
+        self.init_count.load(Ordering::SeqCst)
+    }
+
+    pub fn slow_path_entries(&self) -> usize {
+        self.slow_path_entries.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for LazyValue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn double_checked_init(threads: usize) -> (i32, usize) {
+    let lazy = LazyValue::new();
+
+    let seen: Vec<i32> = thread::scope(|s| {
+        let handles: Vec<_> = (0..threads).map(|_| s.spawn(|| lazy.get())).collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let value = seen.first().copied().unwrap_or(0);
+    (value, lazy.init_count())
+}
+
+// O valor é gravado em published antes do store com Release na flag, e o caminho rápido lê a flag
+// com Acquire antes de ler published; assim, quem vê true também vê o valor. Na prática,
+// std::sync::OnceLock implementa esse mesmo padrão e deve ser preferido.
+
+// Protegendo os Invariantes de uma Máquina de Estados
+
+// Uma máquina de estados só aceita certas transições: não faz sentido pausar algo que nem começou.
+// Se várias threads podem disparar transições, verificar o estado e depois alterá-lo em passos
+// separados abriria espaço para uma corrida. Guardando o estado em um Arc<Mutex<State>>, cada
+// thread confere se a sua ação é válida e aplica a transição com o bloqueio travado, como uma
+// única operação. Cada thread insiste em uma ação diferente, e as transições aplicadas são
+// registradas no log, dentro do mesmo bloqueio, até completar steps transições.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum State {
+    Idle,
+    Running,
+    Paused,
+}
+
+impl State {
+    pub fn transition(self, action: &str) -> Option<State> {
+        match (self, action) {
+            (State::Idle, "start") => Some(State::Running),
+            (State::Running, "pause") => Some(State::Paused),
+            (State::Paused, "resume") => Some(State::Running),
+            (State::Paused, "stop") => Some(State::Idle),
+            _ => None,
+        }
+    }
+}
+
+pub fn guarded_transition(steps: usize) -> Vec<&'static str> {
+    let machine = Arc::new(Mutex::new((State::Idle, Vec::with_capacity(steps))));
+
+    let handles: Vec<_> = ["start", "pause", "resume", "stop"]
+        .into_iter()
+        .map(|action| {
+            let machine = Arc::clone(&machine);
+            thread::spawn(move || loop {
+                {
+                    let mut guard = machine.lock().unwrap();
+                    let (state, log) = &mut *guard;
+                    if log.len() >= steps {
+                        return;
+                    }
+                    if let Some(next) = state.transition(action) {
+                        *state = next;
+                        log.push(action);
+                    }
+                }
+                thread::yield_now();
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let (_, log) = &*machine.lock().unwrap();
+    log.clone()
+}
+
+// A transição inválida simplesmente não acontece: a thread solta o bloqueio e tenta de novo mais
+// tarde, quando o estado talvez permita a sua ação. Como sempre existe alguma ação válida, a
+// máquina nunca trava.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_intersection_matches_sequential() {
+        let a: Vec<i32> = (0..100).collect();
+        let b: Vec<i32> = (50..150).step_by(3).collect();
+
+        let mut expected: Vec<i32> = a.iter().copied().filter(|x| b.contains(x)).collect();
+        expected.sort();
+
+        assert_eq!(parallel_intersection(a, b, 4), expected);
+    }
+
+    #[test]
+    fn false_sharing_layouts_produce_same_totals() {
+        false_sharing_demo(4, 10_000);
+        false_sharing_demo(0, 10_000);
+        assert_eq!(std::mem::align_of::<PaddedCounter>(), 64);
+    }
+
+    #[test]
+    fn transfer_conserves_total_balance() {
+        assert_eq!(transfer(100, 50, 30), (70, 80));
+    }
+
+    #[test]
+    fn with_lock_counts_across_threads() {
+        let counter = Arc::new(Mutex::new(0));
+        let mut handles = vec![];
+
+        for _ in 0..8 {
+            let counter = Arc::clone(&counter);
+            handles.push(thread::spawn(move || {
+                for _ in 0..100 {
+                    with_lock(&counter, |num| *num += 1);
+                }
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(with_lock(&counter, |num| *num), 800);
+    }
+
+    #[test]
+    fn consistent_lock_order_never_deadlocks() {
+        assert!(deadlock_stress(300));
+    }
+
+    #[test]
+    fn memoizer_computes_each_key_once() {
+        let computations = memoize_demo(32);
+        assert!(computations <= 3, "{computations} cálculos para 3 chaves");
+    }
+
+    #[test]
+    fn progress_snapshots_are_monotonic_and_complete() {
+        let snapshots = progress_tracker(103, 4);
+        assert_eq!(snapshots.last(), Some(&103));
+        assert!(snapshots.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn sharded_count_matches_sequential() {
+        let items: Vec<String> = "a b c a b a d e f d"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        let mut expected = HashMap::new();
+        for item in &items {
+            *expected.entry(item.clone()).or_insert(0) += 1;
+        }
+
+        assert_eq!(sharded_count(items, 3), expected);
+    }
+
+    #[test]
+    fn blocking_queue_delivers_items_in_order() {
+        let expected: Vec<i32> = (0..50).collect();
+        assert_eq!(blocking_queue_demo(2, 50), expected);
+    }
+
+    #[test]
+    fn arc_mutex_is_send_and_sync() {
+        assert_send::<Arc<Mutex<i32>>>();
+        assert_sync::<Arc<Mutex<i32>>>();
+    }
+
+    #[test]
+    fn lock_wait_profile_has_one_entry_per_thread() {
+        assert_eq!(lock_wait_profile(8).len(), 8);
+    }
+
+    #[test]
+    fn race_free_push_keeps_both_writes() {
+        assert_eq!(race_free_push(), vec![1, 2]);
+    }
+
+    #[test]
+    fn rwlock_cache_stores_every_write() {
+        assert_eq!(rwlock_cache(8, 100), 8 * 100);
+        assert_eq!(rwlock_cache(3, 0), 0);
+    }
+
+    #[test]
+    fn nested_mutation_counts_every_thread() {
+        let counter = nested_mutation(11);
+        assert_eq!(counter.hits + counter.misses, 11);
+        assert_eq!(counter, Counter { hits: 6, misses: 5 });
+    }
+
+    #[test]
+    fn atomic_and_mutex_counters_agree_under_load() {
+        assert!(equivalence_stress(16, 10_000));
+        assert_eq!(atomic_counter_total(16, 10_000), 160_000);
+    }
+
+    #[test]
+    fn try_lock_retries_until_every_increment_lands() {
+        // O total de threads * attempts é conferido dentro da própria função.
+        try_lock_contention(8, 1_000);
+    }
+
+    #[test]
+    fn try_lock_contention_never_fails_without_competition() {
+        assert_eq!(try_lock_contention(1, 1_000), 0);
+        assert_eq!(try_lock_contention(0, 1_000), 0);
+    }
+
+    #[test]
+    fn concurrent_counter_is_shared_between_clones() {
+        assert_eq!(counter_across_threads(10), 10);
+    }
+
+    #[test]
+    fn shared_rng_draws_distinct_values() {
+        let draws = shared_rng(8, 500);
+        assert_eq!(draws.len(), 8 * 500);
+
+        let distinct: HashSet<u64> = draws.iter().copied().collect();
+        assert_eq!(distinct.len(), draws.len());
+    }
+
+    #[test]
+    fn lock_grant_order_records_each_thread_once() {
+        let mut order = lock_grant_order(16);
+        order.sort();
+        assert_eq!(order, (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn synchronized_start_releases_threads_together() {
+        let starts = synchronized_start(8);
+        assert_eq!(starts.len(), 8);
+
+        let earliest = starts.iter().min().unwrap();
+        let latest = starts.iter().max().unwrap();
+        assert!(latest.duration_since(*earliest) < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn clamped_counter_respects_the_cap() {
+        assert_eq!(clamped_counter(8, 1_000), 1_000);
+        assert_eq!(clamped_counter(1, 5), 5);
+    }
+
+    #[test]
+    fn fairness_scenarios_process_all_units() {
+        let expected = 4 * FAIRNESS_UNITS_PER_THREAD;
+        assert_eq!(run_fairness(4, true).iter().sum::<usize>(), expected);
+        assert_eq!(run_fairness(4, false).iter().sum::<usize>(), expected);
+
+        let (yielding_min, _) = fairness_comparison(4);
+        assert!(yielding_min > 0, "uma thread não processou nenhuma unidade");
+        assert_eq!(
+            fairness_comparison(1),
+            (FAIRNESS_UNITS_PER_THREAD, FAIRNESS_UNITS_PER_THREAD)
+        );
+    }
+
+    #[test]
+    fn condvar_broadcast_wakes_every_subscriber() {
+        assert_eq!(condvar_broadcast(10), 10);
+    }
+
+    #[test]
+    fn sliding_rate_limit_caps_requests_within_a_window() {
+        assert_eq!(sliding_rate_limit(100, 10, 10_000), 10);
+        assert_eq!(sliding_rate_limit(5, 10, 10_000), 5);
+    }
+
+    #[test]
+    fn concurrent_string_build_contains_every_fragment() {
+        let built = concurrent_string_build(12);
+        assert_eq!(built.lines().count(), 12);
+        for i in 0..12 {
+            assert!(built.contains(&format!("fragmento da thread {i:03}")));
+        }
+    }
+
+    #[test]
+    fn nested_arc_shared_counts_every_level() {
+        assert_eq!(nested_arc_shared(20), 20);
+    }
+
+    #[test]
+    fn least_busy_balancer_spreads_load() {
+        let jobs = vec![8, 2, 2, 6, 4, 4, 2, 8, 6, 2, 4, 2];
+        let assignments = least_busy_balancer(jobs.clone(), 3);
+        assert_eq!(assignments.len(), jobs.len());
+
+        let mut totals = [0u64; 3];
+        for (cost, worker) in jobs.iter().zip(&assignments) {
+            totals[*worker] += cost;
+        }
+        let spread = totals.iter().max().unwrap() - totals.iter().min().unwrap();
+        assert!(spread <= 8, "cargas desequilibradas: {totals:?}");
+    }
+
+    #[test]
+    fn replay_log_has_gapless_sequence() {
+        let log = replay_log(500);
+        let sequence: Vec<usize> = log.iter().map(|&(_, seq)| seq).collect();
+        assert_eq!(sequence, (0..1000).collect::<Vec<_>>());
+        assert_eq!(log.iter().filter(|&&(id, _)| id == 0).count(), 500);
+        assert_eq!(log.iter().filter(|&&(id, _)| id == 1).count(), 500);
+    }
+
+    #[test]
+    fn shared_vec_reuse_avoids_reallocation_when_preallocated() {
+        assert_eq!(shared_vec_reuse(4, 250), 0);
+        assert_eq!(shared_vec_reuse(0, 250), 0);
+    }
+
+    #[test]
+    fn elect_leader_returns_a_participant() {
+        assert!(elect_leader(8) < 8);
+        assert_eq!(elect_leader(1), 0);
+    }
+
+    #[test]
+    fn phased_computation_times_each_phase() {
+        let durations = phased_computation(4, 5);
+        assert_eq!(durations.len(), 5);
+        assert!(durations.iter().all(|d| *d > Duration::ZERO));
+    }
+
+    #[test]
+    fn stack_throughput_stacks_hold_every_push() {
+        let lock_free = TreiberStack::new();
+        let locked = LockedStack::new();
+        timed_pushes(4, 1_000, |i| lock_free.push(i));
+        timed_pushes(4, 1_000, |i| locked.push(i));
+        assert_eq!(lock_free.len(), 4_000);
+        assert_eq!(locked.len(), lock_free.len());
+
+        let mut lock_free = lock_free.into_vec();
+        let mut locked = locked.into_vec();
+        lock_free.sort();
+        locked.sort();
+        assert_eq!(lock_free, locked);
+    }
+
+    #[test]
+    fn stack_throughput_times_both_stacks() {
+        let (lock_free, locked) = stack_throughput(2, 100);
+        assert!(lock_free > Duration::ZERO && locked > Duration::ZERO);
+    }
+
+    #[test]
+    fn pool_demo_reuses_objects_within_capacity() {
+        let pool = ObjectPool::new(vec![0, 1, 2]);
+        assert_eq!(pool_workers(&pool, 6), 6 * POOL_ROUNDS);
+        assert!((1..=3).contains(&pool.peak()));
+
+        let mut returned: Vec<usize> = (0..3).map(|_| pool.acquire().unwrap()).collect();
+        returned.sort();
+        assert_eq!(returned, vec![0, 1, 2]);
+        assert!(pool.acquire().is_none());
+    }
+
+    #[test]
+    fn pool_demo_counts_every_acquisition() {
+        assert_eq!(pool_demo(3, 6), 6 * POOL_ROUNDS);
+        assert_eq!(pool_demo(0, 4), 0);
+    }
+
+    #[test]
+    fn backoff_counter_reaches_expected_total() {
+        let (total, _) = backoff_counter(4, 500);
+        assert_eq!(total, 2_000);
+        assert_eq!(backoff_counter(1, 10), (10, 0));
+    }
+
+    #[test]
+    fn double_checked_init_runs_initializer_once() {
+        assert_eq!(double_checked_init(16), (42, 1));
+    }
+
+    #[test]
+    fn lazy_value_fast_path_skips_the_lock() {
+        let lazy = LazyValue::new();
+        assert_eq!(lazy.get(), 42);
+        assert_eq!(lazy.slow_path_entries(), 1);
+
+        thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(|| assert_eq!(lazy.get(), 42));
+            }
+        });
+        assert_eq!(lazy.slow_path_entries(), 1);
+        assert_eq!(lazy.init_count(), 1);
+    }
+
+    #[test]
+    fn guarded_transition_applies_only_valid_transitions() {
+        let log = guarded_transition(20);
+        assert_eq!(log.len(), 20);
+
+        let mut state = State::Idle;
+        for action in log {
+            state = state
+                .transition(action)
+                .unwrap_or_else(|| panic!("transição inválida: {action} a partir de {state:?}"));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod chapter161;
+pub mod chapter162;
+pub mod chapter163;
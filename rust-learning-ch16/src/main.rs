@@ -1,6 +1,6 @@
-mod chapter161;
-mod chapter162;
-mod chapter163;
+// Os módulos chapter161 e chapter162 só aparecem nas chamadas comentadas abaixo.
+#[allow(unused_imports)]
+use rust_learning_ch16::{chapter161, chapter162, chapter163};
 
 fn main() {
     // chapter161::spawn_thread();
@@ -8,9 +8,109 @@ fn main() {
     // chapter161::waiting_thread();
 
     // chapter161::handle_thread()
+    // chapter161::Scheduler::new().run();
+    // println!("{}", chapter161::all_or_restart(4, 1));
+    // println!("{:?}", chapter161::simulate_downloads(vec![String::from("https://example.com")], 2));
+    // println!("{:?}", chapter161::fan_out(vec![1, 2, 3], |x| x * 2));
+    // println!("{:?}", chapter161::try_fan_out(vec![1, -2, 3], |x| if x < 0 { Err(format!("{x}")) } else { Ok(x) }));
+    // println!("{}", chapter161::parallel_fib(25, 15));
+    // println!("{:?}", chapter161::process_lines(vec![String::from("hi"), String::from("hello")], 2));
+    // println!("{}", chapter161::local_accumulate(4, 1_000));
+    // println!("{:?}", chapter161::jittered_threads(42));
+    // println!("{:?}", chapter161::frame_budget(vec![5, 5, 10, 5], 16));
+    // chapter161::parallel_quicksort(&mut vec![3, 1, 2], 2);
+    // println!("{:?}", chapter161::parallel_prefix_sum(vec![1, 2, 3, 4], 2));
+    // println!("{:?}", chapter161::runaway_guard(150, 50));
+    // println!("{}", chapter161::nested_scopes());
+    // chapter161::with_callback(3, |value| println!("got {value}"));
+    // println!("{}", chapter161::join_detects_panic());
+    // println!("{:?}", chapter161::collect_typed(3));
+    // chapter161::par_for_each_range(0, 10, 3, |i| println!("{i}"));
+    // println!("{:?}", chapter161::park_unpark_demo());
+    // println!("{:?}", chapter161::scope_partial_recovery(4, 1));
+    // println!("{:?}", chapter161::parallel_dot(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], 2));
+    // println!("{:?}", chapter161::yield_demo(3));
+    // println!("{:?}", chapter161::parallel_find(vec![4, 8, 15, 16, 23, 42], 16, 3));
+    // println!("{:?}", chapter161::spawn_vs_pool(1000));
 
     // chapter162::create_channel();
     // chapter162::move_channel()
     // chapter162::recover_channel()
+    // println!("{:?}", chapter162::fallible_pipeline(vec![1, -2, 3]));
+    // println!("{}", chapter162::at_least_once(10));
+    // println!("{:?}", chapter162::stream_transform(vec![1, 2, 3, 4]));
+    // println!("{:?}", chapter162::broadcast_replay());
+    // println!("{:?}", chapter162::channel_latency(10));
+    // println!("{:?}", chapter162::streaming_primes(50));
+    // println!("{}", chapter162::transfer_ownership());
+    // println!("{:?}", chapter162::bounded_time_collect(vec![10, 500, 20], 100));
+    // println!("{:?}", chapter162::ping_pong(10_000));
+    // println!("{:?}", chapter162::indexed_results(4));
+    // println!("{:?}", chapter162::shared_consumer(4, 100));
+    // println!("{:?}", chapter162::coalesce(20, 12));
+    // println!("{:?}", chapter162::explicit_drop_to_close());
+    // println!("{:?}", chapter162::merge_sorted_streams(vec![vec![1, 3], vec![2, 4]]));
+    // println!("{}", chapter162::timer_demo(true));
+    // println!("{}", chapter162::adaptive_send(1, 20, 2));
+    // println!("{:?}", chapter162::rolling_window(vec![1, 2, 3, 4, 5], 3));
+    // println!("{}", chapter162::map_filter_reduce((1..=10).collect(), 3));
+    // println!("{:?}", chapter162::query_server(5));
+    // println!("{}", chapter162::race_to_finish(vec![50, 10, 30]));
+    // println!("{:?}", chapter162::worker_throughput(4, 1_000));
+    // println!("{:?}", chapter162::zero_copy_broadcast(vec![0; 1024], 4));
+    // println!("{}", chapter162::credit_flow(100, 4));
+    // println!("{:?}", chapter162::run_batch(vec![1, -2, 3]));
+    // println!("{:?}", chapter162::first_n_results(vec![10, 50, 20, 40], 2));
+    // println!("{:?}", chapter162::bounded_pipeline(vec![1, 2, 3], 1));
+    // println!("{:?}", chapter162::state_machine(vec!["start", "tick", "stop"]));
+    // println!("{:?}", chapter162::sentinel_drain());
+    // println!("{:?}", chapter162::timing_percentiles(vec![5, 10, 15, 20]));
+    // let (subscription, rx) = chapter162::subscribe();
+    // println!("{:?}", rx.recv());
+    // subscription.cancel();
+    // println!("{}", chapter162::detect_duplicates(true));
+    // println!("{}", chapter162::fan_out_shutdown(4, 100));
+    // println!("{:?}", chapter162::classify_channel_error(true));
+    // println!("{:?}", chapter162::two_pass_normalize(vec![2.0, 4.0, 6.0, 10.0], 2));
+    // println!("{:?}", chapter162::group_by_parity(vec![1, 2, 3, 4, 5], 2));
+    // println!("{:?}", chapter162::interruptible_recv(50));
+    // println!("{:?}", chapter162::frame_pipeline(5, 3));
+    // println!("{:?}", chapter162::progressive_aggregate(vec![1, 2, 3, 4], |a, b| a + b));
+    // println!("{:?}", chapter162::ordered_shutdown());
+    // println!("{:?}", chapter162::parallel_count_distinct(vec![1, 2, 2, 3, 3, 3], 2));
     chapter163::atom_ref()
+    // println!("{:?}", chapter163::parallel_intersection(vec![1, 2, 3, 4], vec![2, 4, 6], 2));
+    // println!("{:?}", chapter163::false_sharing_demo(4, 1_000_000));
+    // println!("{:?}", chapter163::transfer(100, 50, 30));
+    // println!("{}", chapter163::deadlock_stress(1_000));
+    // println!("{}", chapter163::memoize_demo(16));
+    // println!("{:?}", chapter163::progress_tracker(100, 4));
+    // println!("{:?}", chapter163::sharded_count(vec![String::from("a"), String::from("b"), String::from("a")], 2));
+    // println!("{:?}", chapter163::blocking_queue_demo(2, 10));
+    // println!("{:?}", chapter163::lock_wait_profile(8));
+    // println!("{:?}", chapter163::race_free_push());
+    // println!("{}", chapter163::rwlock_cache(8, 100));
+    // println!("{:?}", chapter163::nested_mutation(10));
+    // println!("{}", chapter163::equivalence_stress(8, 10_000));
+    // println!("{}", chapter163::try_lock_contention(8, 1_000));
+    // println!("{}", chapter163::counter_across_threads(10));
+    // println!("{:?}", chapter163::shared_rng(2, 3));
+    // println!("{:?}", chapter163::lock_grant_order(8));
+    // println!("{:?}", chapter163::synchronized_start(4));
+    // println!("{}", chapter163::clamped_counter(8, 100));
+    // println!("{:?}", chapter163::fairness_comparison(4));
+    // println!("{}", chapter163::condvar_broadcast(5));
+    // println!("{}", chapter163::sliding_rate_limit(100, 10, 1_000));
+    // println!("{}", chapter163::concurrent_string_build(4));
+    // println!("{}", chapter163::nested_arc_shared(10));
+    // println!("{:?}", chapter163::least_busy_balancer(vec![8, 2, 2, 6, 4], 2));
+    // println!("{:?}", chapter163::replay_log(3));
+    // println!("{:?}", chapter163::shared_vec_reuse(4, 100));
+    // println!("{:?}", chapter163::elect_leader(4));
+    // println!("{:?}", chapter163::phased_computation(4, 3));
+    // println!("{:?}", chapter163::stack_throughput(4, 10_000));
+    // println!("{:?}", chapter163::pool_demo(2, 4));
+    // println!("{:?}", chapter163::backoff_counter(4, 1000));
+    // println!("{:?}", chapter163::double_checked_init(8));
+    // println!("{:?}", chapter163::guarded_transition(8));
 }